@@ -55,7 +55,7 @@ macro_rules! MulTEPrim {
 }
 
 impl<T> MulTE<T, ()> for MulTEDefaultType {
-    fn mul(_ : &T, _ : &()) -> () {} 
+    fn mul(_ : &T, _ : &()) {}
 }
 
 MulTEPrim!(u8, usize);
@@ -0,0 +1,94 @@
+//! 库中使用的错误类型
+//!
+
+use std::fmt;
+
+/// 图的构建、求解和输入输出过程中可能出现的错误
+#[derive(Debug)]
+pub enum FlowError {
+    /// 点的编号超出了范围
+    InvalidNode(usize),
+    /// 边的编号不是图中的正向边
+    InvalidEdge(usize),
+    /// 标签对应的点不存在
+    UnknownLabel,
+    /// 文件的内容不符合格式，附带说明
+    MalformedFile(String),
+    /// 无法将字符串解析为对应的类型，附带无法解析的字符串
+    Parse(String),
+    /// 输入在读取完成之前结束
+    TruncatedInput,
+    /// 不支持的图的类型或结构，附带说明
+    UnsupportedGraph(String),
+    /// 流量的约束无法被满足，附带违反约束的点
+    Infeasible(Vec<usize>),
+    /// 残量网络中存在负环，附带环上的点
+    NegativeCycle(Vec<usize>),
+    /// 计算过程中的数值溢出或者无法在类型之间转换
+    Overflow,
+    /// 读写时发生的错误
+    Io(std::io::Error),
+    /// 文本文件中第几行(从1开始)出现的错误
+    Line(usize, Box<FlowError>)
+}
+
+impl fmt::Display for FlowError {
+    fn fmt(&self, f : &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlowError::InvalidNode(x) => write!(f, "invalid node index {}", x),
+            FlowError::InvalidEdge(x) => write!(f, "invalid edge index {}", x),
+            FlowError::UnknownLabel => write!(f, "unknown node label"),
+            FlowError::MalformedFile(s) => write!(f, "malformed file: {}", s),
+            FlowError::Parse(s) => write!(f, "cannot parse {:?}", s),
+            FlowError::TruncatedInput => write!(f, "unexpected end of input"),
+            FlowError::UnsupportedGraph(s) => write!(f, "unsupported graph: {}", s),
+            FlowError::Infeasible(v) => write!(f, "infeasible constraints at nodes {:?}", v),
+            FlowError::NegativeCycle(v) => write!(f, "negative cycle through nodes {:?}", v),
+            FlowError::Overflow => write!(f, "numeric overflow"),
+            FlowError::Io(e) => write!(f, "io error: {}", e),
+            FlowError::Line(line, e) => write!(f, "line {}: {}", line, e)
+        }
+    }
+}
+
+impl std::error::Error for FlowError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FlowError::Io(e) => Some(e),
+            FlowError::Line(_, e) => Some(e.as_ref()),
+            _ => None
+        }
+    }
+}
+
+impl From<std::io::Error> for FlowError {
+    fn from(e : std::io::Error) -> Self {
+        if e.kind() == std::io::ErrorKind::UnexpectedEof {
+            FlowError::TruncatedInput
+        }
+        else {
+            FlowError::Io(e)
+        }
+    }
+}
+
+/// 读写错误只比较错误的种类
+impl PartialEq for FlowError {
+    fn eq(&self, other : &Self) -> bool {
+        match (self, other) {
+            (FlowError::InvalidNode(a), FlowError::InvalidNode(b)) => a == b,
+            (FlowError::InvalidEdge(a), FlowError::InvalidEdge(b)) => a == b,
+            (FlowError::UnknownLabel, FlowError::UnknownLabel) => true,
+            (FlowError::MalformedFile(a), FlowError::MalformedFile(b)) => a == b,
+            (FlowError::Parse(a), FlowError::Parse(b)) => a == b,
+            (FlowError::TruncatedInput, FlowError::TruncatedInput) => true,
+            (FlowError::UnsupportedGraph(a), FlowError::UnsupportedGraph(b)) => a == b,
+            (FlowError::Infeasible(a), FlowError::Infeasible(b)) => a == b,
+            (FlowError::NegativeCycle(a), FlowError::NegativeCycle(b)) => a == b,
+            (FlowError::Overflow, FlowError::Overflow) => true,
+            (FlowError::Io(a), FlowError::Io(b)) => a.kind() == b.kind(),
+            (FlowError::Line(a, x), FlowError::Line(b, y)) => a == b && x == y,
+            _ => false
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! 图的二进制格式
+//!
+//! 依次为8字节的魔数`NFLOWBIN`、u32的版本号、图的内容，以及对之前所有字节计算的
+//! 64位FNV-1a校验和。所有整数都使用固定宽度的大端序，点和边的编号均以u64存储，
+//! 不存在的边存储为u64::MAX。
+//!
+//! 图的内容为点数，每个点的标签、供给和需求，每个点的邻接表中第一条边；
+//! 之后为边数，每条边的起点、终点、邻接表中的下一条边、是否为反向边、残量、没有流量时的残量、费用和下界。
+//! 标签和边上的各个值由BitIO转为字节，并以u64的长度开头。
+
+use super::Graph;
+use super::edge::Edge;
+use crate::error::FlowError;
+use crate::io::BitIO;
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{BufReader, BufWriter, Read, Write};
+
+const MAGIC : &[u8; 8] = b"NFLOWBIN";
+/// 格式的版本，读取时只接受相同版本的文件
+const VERSION : u32 = 1;
+
+const FNV_OFFSET : u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME : u64 = 0x0000_0100_0000_01b3;
+
+fn fnv(mut hash : u64, buf : &[u8]) -> u64 {
+    for &b in buf {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+// 在写入的同时计算校验和
+struct HashWriter<W> {
+    inner : W,
+    hash : u64
+}
+
+impl<W : Write> Write for HashWriter<W> {
+    fn write(&mut self, buf : &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.hash = fnv(self.hash, &buf[..n]);
+        Ok(n)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+// 在读取的同时计算校验和
+struct HashReader<R> {
+    inner : R,
+    hash : u64
+}
+
+impl<R : Read> Read for HashReader<R> {
+    fn read(&mut self, buf : &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hash = fnv(self.hash, &buf[..n]);
+        Ok(n)
+    }
+}
+
+fn write_u64<W : Write>(w : &mut W, x : u64) -> Result<(), FlowError> {
+    w.write_all(&x.to_be_bytes())?;
+    Ok(())
+}
+
+fn write_index<W : Write>(w : &mut W, x : usize) -> Result<(), FlowError> {
+    write_u64(w, if x == usize::MAX { u64::MAX } else { x as u64 })
+}
+
+fn write_bytes<W : Write>(w : &mut W, a : &[u8]) -> Result<(), FlowError> {
+    write_u64(w, a.len() as u64)?;
+    w.write_all(a)?;
+    Ok(())
+}
+
+fn read_u64<R : Read>(r : &mut R) -> Result<u64, FlowError> {
+    let mut buf = [0; 8];
+    r.read_exact(&mut buf)?;
+    Ok(u64::from_be_bytes(buf))
+}
+
+// 读取一个编号，无法在当前平台上表示时返回错误
+fn read_index<R : Read>(r : &mut R) -> Result<usize, FlowError> {
+    match read_u64(r)? {
+        u64::MAX => Ok(usize::MAX),
+        x => usize::try_from(x).map_err(|_| FlowError::MalformedFile(String::from("index out of range")))
+    }
+}
+
+// 读取一段以长度开头的字节
+fn read_bytes<R : Read>(r : &mut R) -> Result<Vec<u8>, FlowError> {
+    let len = read_u64(r)?;
+    let mut buf = vec![];
+    r.take(len).read_to_end(&mut buf)?;
+    if buf.len() as u64 != len {
+        return Err(FlowError::TruncatedInput);
+    }
+    Ok(buf)
+}
+
+impl<L, T, E, M> Graph<L, T, E, M>
+    where
+        L : BitIO + Clone + Hash + Eq,
+        E : BitIO + Clone + Default,
+        T : BitIO + Clone + Default,
+        M : crate::costtype::MulTE<T, E> {
+    /// 将当前的图的状态写入w中
+    ///
+    /// L, T, E均需实现BitIO trait。格式见[`Graph::read_from`]
+    pub fn write_to<W : Write>(&self, w : W) -> Result<(), FlowError> {
+        let mut w = HashWriter { inner : w, hash : FNV_OFFSET };
+        w.write_all(MAGIC)?;
+        w.write_all(&VERSION.to_be_bytes())?;
+        write_u64(&mut w, self.labels.len() as u64)?;
+        for i in 0..self.labels.len() {
+            write_bytes(&mut w, &self.labels[i].to_bit())?;
+            write_bytes(&mut w, &self.node_supply(i).to_bit())?;
+            write_bytes(&mut w, &self.node_demand(i).to_bit())?;
+            write_index(&mut w, self.first[i].next_edge)?;
+        }
+        write_u64(&mut w, self.edges.len() as u64)?;
+        for edge in &self.edges {
+            write_index(&mut w, edge.from)?;
+            write_index(&mut w, edge.to)?;
+            write_index(&mut w, edge.next_edge)?;
+            w.write_all(&[edge.reversed as u8])?;
+            write_bytes(&mut w, &edge.weight.to_bit())?;
+            write_bytes(&mut w, &edge.capacity.to_bit())?;
+            write_bytes(&mut w, &edge.cost.to_bit())?;
+            write_bytes(&mut w, &edge.lower.to_bit())?;
+        }
+        let hash = w.hash;
+        w.inner.write_all(&hash.to_be_bytes())?;
+        w.inner.flush()?;
+        Ok(())
+    }
+
+    /// 从r中读取一个图
+    ///
+    /// 以`NFLOWBIN`开头，之后是版本号、图的内容和FNV-1a校验和，整数均为固定宽度的大端序，
+    /// 因此在不同的平台之间通用。输入不完整时返回FlowError::TruncatedInput，
+    /// 魔数、版本号、校验和或者图的结构不合法时返回FlowError::MalformedFile
+    ///
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// use network_flow::error::FlowError;
+    /// let mut g = Graph::<String, u32, i32>::new();
+    /// g.add_edge2_by_label(&"s".to_string(), &"t".to_string(), &3, &2);
+    /// let mut buf = vec![];
+    /// g.write_to(&mut buf).unwrap();
+    /// let mut h = Graph::<String, u32, i32>::read_from(&buf[..]).unwrap();
+    /// assert_eq!(h.mcmf(0, 1), Ok((3, 6)));
+    /// let n = buf.len();
+    /// buf[n - 9] ^= 1;
+    /// assert!(matches!(Graph::<String, u32, i32>::read_from(&buf[..]), Err(FlowError::MalformedFile(_))));
+    /// ```
+    pub fn read_from<R : Read>(r : R) -> Result<Self, FlowError> {
+        let mut r = HashReader { inner : r, hash : FNV_OFFSET };
+        let mut magic = [0; 8];
+        r.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(FlowError::MalformedFile(String::from("bad magic number")));
+        }
+        let mut version = [0; 4];
+        r.read_exact(&mut version)?;
+        let version = u32::from_be_bytes(version);
+        if version != VERSION {
+            return Err(FlowError::MalformedFile(format!("unsupported version {}", version)));
+        }
+        let mut res = Self::new();
+        let n = read_u64(&mut r)?;
+        for i in 0..n {
+            res.labels.push(L::from_bit(&read_bytes(&mut r)?)?);
+            res.supply.push(T::from_bit(&read_bytes(&mut r)?)?);
+            res.demand.push(T::from_bit(&read_bytes(&mut r)?)?);
+            let mut first = Edge::empty_edge(i as usize);
+            first.next_edge = read_index(&mut r)?;
+            res.first.push(first);
+        }
+        let m = read_u64(&mut r)?;
+        for i in 0..m {
+            let from = read_index(&mut r)?;
+            let to = read_index(&mut r)?;
+            let next_edge = read_index(&mut r)?;
+            let mut reversed = [0];
+            r.read_exact(&mut reversed)?;
+            let weight = T::from_bit(&read_bytes(&mut r)?)?;
+            let capacity = T::from_bit(&read_bytes(&mut r)?)?;
+            let cost = E::from_bit(&read_bytes(&mut r)?)?;
+            let lower = T::from_bit(&read_bytes(&mut r)?)?;
+            let opp_edge = i as usize ^ 1;
+            res.edges.push(Edge { from, to, next_edge, opp_edge, reversed : reversed[0] != 0, weight, capacity, cost, lower });
+        }
+        let hash = r.hash;
+        if read_u64(&mut r.inner)? != hash {
+            return Err(FlowError::MalformedFile(String::from("checksum mismatch")));
+        }
+        res.check_structure()?;
+        res.hs = super::make_hash(&res.labels);
+        Ok(res)
+    }
+
+    /// 将当前的图的状态输出到文件中，格式与write_to相同
+    ///
+    /// L, T, E均需实现BitIO trait
+    pub fn output_file(&self, file : &str) -> Result<(), FlowError> {
+        self.write_to(BufWriter::new(File::create(file)?))
+    }
+
+    /// 从output_file输出的文件中生成一个图
+    ///
+    /// L, T, E均需实现BitIO trait，错误与read_from相同
+    pub fn input_file(file : &str) -> Result<Self, FlowError> {
+        Self::read_from(BufReader::new(File::open(file)?))
+    }
+}
+
+impl<L : Hash, T, E, M : crate::costtype::MulTE<T, E>> Graph<L, T, E, M> {
+    // 检查读入的边和邻接表是否互相一致，避免之后的计算中出现越界
+    fn check_structure(&self) -> Result<(), FlowError> {
+        let n = self.labels.len();
+        let m = self.edges.len();
+        let bad = |s : &str| Err(FlowError::MalformedFile(String::from(s)));
+        if self.first.len() != n {
+            return bad("node count mismatch");
+        }
+        if !m.is_multiple_of(2) {
+            return bad("invalid reverse edge");
+        }
+        // 被删除的边的两条边都标记为反向边，其端点可能已经失效，与自环的正向边一样不在邻接表中
+        let unlinked = |i : usize| self.is_removed(i) || (!self.edges[i].reversed && self.edges[i].from == self.edges[i].to);
+        for (i, e) in self.edges.iter().enumerate() {
+            if e.opp_edge != (i ^ 1) {
+                return bad("invalid reverse edge");
+            }
+            if self.is_removed(i) {
+                continue;
+            }
+            if e.from >= n || e.to >= n {
+                return bad("edge endpoint out of range");
+            }
+            if e.reversed != (i % 2 == 1) {
+                return bad("invalid reverse edge");
+            }
+            if self.edges[i ^ 1].from != e.to || self.edges[i ^ 1].to != e.from {
+                return bad("invalid reverse edge");
+            }
+            if !unlinked(i) && e.next_edge != usize::MAX && (e.next_edge >= m || self.edges[e.next_edge].from != e.from) {
+                return bad("invalid adjacency list");
+            }
+        }
+        // 其余每条边恰好在其起点的邻接表中出现一次
+        let mut seen = vec![false; m];
+        for (i, f) in self.first.iter().enumerate() {
+            let mut e = f.next_edge;
+            while e != usize::MAX {
+                if e >= m || seen[e] || unlinked(e) || self.edges[e].from != i {
+                    return bad("invalid adjacency list");
+                }
+                seen[e] = true;
+                e = self.edges[e].next_edge;
+            }
+        }
+        if seen.iter().enumerate().any(|(i, &x)| !x && !unlinked(i)) {
+            return bad("invalid adjacency list");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+//! 带有流量下界的可行流、最大流和最小费用最大流
+//!
+//! 使用标准的转化方法：每条边的容量变为upper - lower，
+//! 下界带来的流量差由超级源点和超级汇点补足，有可行解当且仅当超级源点的出边全部满流。
+//!
+//! 无解时返回一个违反条件的割：割中的点必须流入的下界之和超过了能够流出的容量，
+//! 该割即超级源点在残量网络中能到达的点。
+
+use super::Graph;
+use super::edge::Edge;
+use crate::error::FlowError;
+use core::ops::Add;
+use core::ops::Sub;
+use std::hash::Hash;
+
+impl<L, T, E, M> Graph<L, T, E, M>
+    where
+        L : Clone + Hash + Eq,
+        E : Clone + Default + Add<Output = E> + Sub<Output = E> + PartialEq + PartialOrd,
+        T : Clone + Default + Add<Output = T> + Sub<Output = T> + PartialEq + PartialOrd,
+        M : crate::costtype::MulTE<T, E> {
+
+    // 添加超级源点和超级汇点，以及补足流量差的边，返回(超级源点, 超级汇点, 需要的流量)
+    //
+    // 流量差按照每条边上的实际流量计算，因此图中原有的流量也会被保留并修正。
+    // 超级源点和超级汇点不加入标签的哈希表中，之后需要使用truncate_graph删除
+    fn add_super_terminals(&mut self) -> (usize, usize, T) {
+        let n = self.labels.len();
+        let mut flow_in = vec![T::default(); n];
+        let mut flow_out = vec![T::default(); n];
+        for (i, e) in self.edges.iter().enumerate() {
+            if !e.reversed {
+                let f = self.edge_flow(i);
+                flow_in[e.to] = flow_in[e.to].clone() + f.clone();
+                flow_out[e.from] = flow_out[e.from].clone() + f;
+            }
+        }
+        let label = self.labels[0].clone();
+        for i in 0..2 {
+            self.labels.push(label.clone());
+            self.first.push(Edge::empty_edge(n + i));
+        }
+        let (ss, tt) = (n, n + 1);
+        let mut need = T::default();
+        for i in 0..n {
+            if flow_in[i] > flow_out[i] {
+                let d = flow_in[i].clone() - flow_out[i].clone();
+                need = need + d.clone();
+                self.push_edge(ss, i, &d, &E::default());
+            }
+            else if flow_out[i] > flow_in[i] {
+                self.push_edge(i, tt, &(flow_out[i].clone() - flow_in[i].clone()), &E::default());
+            }
+        }
+        (ss, tt, need)
+    }
+
+    // 删除编号不小于nodes的点和编号不小于edges的边
+    //
+    // 只能用于删除最后添加的、且不在标签哈希表中的点，删除的边需要按照添加的相反顺序进行。
+    // 如果给出了saved，则将剩余的边的残量恢复为saved中的值
+    fn truncate_graph(&mut self, nodes : usize, edges : usize, saved : Option<Vec<T>>) {
+        while self.edges.len() > edges {
+            let e = self.edges.pop().unwrap();
+            if e.from < nodes {
+                self.first[e.from].next_edge = e.next_edge;
+            }
+        }
+        self.labels.truncate(nodes);
+        self.first.truncate(nodes);
+        if let Some(saved) = saved {
+            for (e, w) in self.edges.iter_mut().zip(saved) {
+                e.weight = w;
+            }
+        }
+    }
+
+    fn save_weights(&self) -> Vec<T> {
+        self.edges.iter().map(|e| e.weight.clone()).collect()
+    }
+
+    // 超级源点在残量网络中可以到达的原图中的点
+    fn violating_cut(&self, ss : usize, n : usize) -> Vec<usize> {
+        self.reachable(ss).into_iter().filter(|&x| x < n).collect()
+    }
+
+    // 添加一条从t到s的容量足够大的边，使得s到t的流可以变为循环流
+    fn add_return_edge(&mut self, s : usize, t : usize) {
+        let mut inf = T::default();
+        for e in &self.edges {
+            if !e.reversed && e.from == s {
+                inf = inf + e.weight.clone() + self.edges[e.opp_edge].weight.clone() + e.lower.clone();
+            }
+        }
+        self.push_edge(t, s, &inf, &E::default());
+    }
+
+    /// 求满足所有边的流量下界和上界的可行循环流
+    ///
+    /// 图中原有的流量会作为初始值被修正。有解时每条边上超过下界的流量保存在残量网络中；
+    /// 无解时返回FlowError::Infeasible以及一个违反条件的割，即一个点集，其中的点必须流入的流量超过了能够从点集中流出的容量。
+    ///
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// use network_flow::error::FlowError;
+    /// let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2]);
+    /// g.add_edge_bounded(0, 1, &2, &5, &0).unwrap();
+    /// g.add_edge_bounded(1, 2, &0, &3, &0).unwrap();
+    /// g.add_edge_bounded(2, 0, &0, &4, &0).unwrap();
+    /// assert_eq!(g.feasible_circulation(), Ok(()));
+    /// g.add_edge_bounded(1, 0, &6, &6, &0).unwrap();
+    /// assert_eq!(g.feasible_circulation(), Err(FlowError::Infeasible(vec![0])));
+    /// ```
+    pub fn feasible_circulation(&mut self) -> Result<(), FlowError> {
+        let n = self.labels.len();
+        if n == 0 {
+            return Ok(());
+        }
+        let m = self.edges.len();
+        let saved = self.save_weights();
+        let (ss, tt, need) = self.add_super_terminals();
+        if self.dinic(ss, tt) != need {
+            let cut = self.violating_cut(ss, n);
+            self.truncate_graph(n, m, Some(saved));
+            return Err(FlowError::Infeasible(cut));
+        }
+        self.truncate_graph(n, m, None);
+        Ok(())
+    }
+
+    /// 求从s到t的满足所有边流量下界的最大流
+    ///
+    /// 返回的流量包含了下界的部分；无解时返回一个违反条件的割，与feasible_circulation相同。
+    ///
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2, 3]);
+    /// g.add_edge_bounded(0, 1, &0, &4, &0).unwrap();
+    /// g.add_edge_bounded(1, 3, &0, &2, &0).unwrap();
+    /// g.add_edge_bounded(1, 2, &2, &3, &0).unwrap();
+    /// g.add_edge_bounded(2, 3, &0, &5, &0).unwrap();
+    /// assert_eq!(g.max_flow_bounded(0, 3), Ok(4));
+    /// ```
+    pub fn max_flow_bounded(&mut self, s : usize, t : usize) -> Result<T, FlowError> {
+        self.check_node(s)?;
+        self.check_node(t)?;
+        let n = self.labels.len();
+        let m = self.edges.len();
+        let saved = self.save_weights();
+        self.add_return_edge(s, t);
+        let (ss, tt, need) = self.add_super_terminals();
+        if self.dinic(ss, tt) != need {
+            let cut = self.violating_cut(ss, n);
+            self.truncate_graph(n, m, Some(saved));
+            return Err(FlowError::Infeasible(cut));
+        }
+        let f = self.edges[m + 1].weight.clone();
+        self.truncate_graph(n, m, None);
+        Ok(f + self.dinic(s, t))
+    }
+
+    /// 求从s到t的满足所有边流量下界的最小费用最大流
+    ///
+    /// 先在加入t到s的边的图上求出可行流，去掉这条边之后用Dinic增广到最大流，
+    /// 最后用min_cost_circulation消去残量网络中的负环，因此费用可以为负数，图中也可以有负环。
+    /// 返回的(流量, 费用)包含了下界的部分；无解时返回一个违反条件的割，与feasible_circulation相同。
+    ///
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2, 3]);
+    /// g.add_edge_bounded(0, 1, &0, &2, &1).unwrap();
+    /// g.add_edge_bounded(0, 2, &0, &2, &3).unwrap();
+    /// g.add_edge_bounded(1, 3, &0, &2, &1).unwrap();
+    /// g.add_edge_bounded(2, 3, &1, &2, &1).unwrap();
+    /// assert_eq!(g.mcmf_bounded(0, 3), Ok((4, 12)));
+    /// ```
+    pub fn mcmf_bounded(&mut self, s : usize, t : usize) -> Result<(T, E), FlowError> {
+        let f = self.max_flow_bounded(s, t)?;
+        self.min_cost_circulation();
+        let mut cost = E::default();
+        for (i, e) in self.edges.iter().enumerate() {
+            if !e.reversed {
+                cost = cost + M::mul(&self.edge_flow(i), &e.cost);
+            }
+        }
+        Ok((f, cost))
+    }
+}
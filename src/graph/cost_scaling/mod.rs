@@ -0,0 +1,164 @@
+//! 费用缩放(Goldberg-Tarjan)算法求最小费用最大流
+//!
+//! 先用Dinic求出一个最大流，再在残量网络上求最小费用循环流，
+//! 循环流不会改变每个点的流量平衡，因此得到的是最小费用最大流。
+//!
+//! 只适用于容量和费用都是整数的情况，计算时转为i128进行。
+
+use super::Graph;
+use crate::error::FlowError;
+use core::ops::Add;
+use core::ops::Sub;
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+// 每一轮中epsilon缩小的倍数
+const ALPHA : i128 = 8;
+
+impl<L, T, E, M> Graph<L, T, E, M>
+    where
+        L : Clone + Hash + Eq,
+        E : Clone + Default + Add<Output = E> + Sub<Output = E> + PartialEq + PartialOrd + TryInto<i128> + TryFrom<i128>,
+        T : Clone + Default + Add<Output = T> + Sub<Output = T> + PartialEq + PartialOrd + TryInto<i128> + TryFrom<i128>,
+        M : crate::costtype::MulTE<T, E> {
+
+    // 在epsilon下的一轮细化：先将所有约化费用为负的残量边推满，再用推流-重标号消去所有超额流
+    fn refine(&self, cap : &mut [i128], cost : &[i128], p : &mut [i128], eps : i128) {
+        let n = self.labels.len();
+        let mut excess = vec![0i128; n];
+        for u in 0..n {
+            let mut e = self.first[u].next_edge;
+            while e != usize::MAX {
+                let v = self.edges[e].to;
+                if cap[e] > 0 && cost[e] + p[u] - p[v] < 0 {
+                    let f = cap[e];
+                    cap[e] = 0;
+                    cap[self.edges[e].opp_edge] += f;
+                    excess[u] -= f;
+                    excess[v] += f;
+                }
+                e = self.edges[e].next_edge;
+            }
+        }
+        let mut current : Vec<usize> = self.first.iter().map(|x| x.next_edge).collect();
+        let mut q : VecDeque<usize> = (0..n).filter(|&x| excess[x] > 0).collect();
+        while let Some(u) = q.pop_front() {
+            while excess[u] > 0 {
+                let e = current[u];
+                if e == usize::MAX {
+                    // 重标号，使至少一条残量边变为可推流的边
+                    let mut best = i128::MIN;
+                    let mut e = self.first[u].next_edge;
+                    while e != usize::MAX {
+                        if cap[e] > 0 {
+                            best = best.max(p[self.edges[e].to] - cost[e]);
+                        }
+                        e = self.edges[e].next_edge;
+                    }
+                    p[u] = best - eps;
+                    current[u] = self.first[u].next_edge;
+                    continue;
+                }
+                let v = self.edges[e].to;
+                if cap[e] > 0 && cost[e] + p[u] - p[v] < 0 {
+                    let f = excess[u].min(cap[e]);
+                    cap[e] -= f;
+                    cap[self.edges[e].opp_edge] += f;
+                    excess[u] -= f;
+                    if excess[v] <= 0 && excess[v] + f > 0 {
+                        q.push_back(v);
+                    }
+                    excess[v] += f;
+                    if cap[e] == 0 {
+                        current[u] = self.edges[e].next_edge;
+                    }
+                }
+                else {
+                    current[u] = self.edges[e].next_edge;
+                }
+            }
+        }
+    }
+
+    /// 使用费用缩放算法求从s到t的最小费用最大流
+    ///
+    /// 容量和费用都需要是整数类型。算法将费用乘以n + 1后进行缩放，
+    /// 最后一轮结束时得到的流是1/(n + 1)-最优的，对于整数费用即为最优解。
+    ///
+    /// 返回(流量, 费用, 每个点的势)，势h满足对残量网络中的每条边都有c(u, v) + h(u) - h(v) >= 0。
+    /// 如果计算过程中的数值无法转换为i128或者无法转换回T和E，返回FlowError::Overflow，此时图不会被修改。
+    ///
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2]);
+    /// g.add_edge2(0, 1, &2, &1).unwrap();
+    /// g.add_edge2(1, 2, &1, &3).unwrap();
+    /// g.add_edge2(0, 2, &1, &5).unwrap();
+    /// let (flow, cost, _) = g.mcmf_cost_scaling(0, 2).unwrap();
+    /// assert_eq!((flow, cost), (2, 9));
+    /// ```
+    pub fn mcmf_cost_scaling(&mut self, s : usize, t : usize) -> Result<(T, E, Vec<E>), FlowError> {
+        self.check_node(s)?;
+        self.check_node(t)?;
+        let n = self.labels.len() as i128;
+        let m = self.edges.len();
+        // 先完成求最大流之前能做的转换，失败时图不会被修改
+        let mut before = Vec::with_capacity(m);
+        let mut cost = Vec::with_capacity(m);
+        let mut eps = 0;
+        for i in 0..m {
+            let w : i128 = self.edges[i].weight.clone().try_into().map_err(|_| FlowError::Overflow)?;
+            let c : i128 = self.arc_cost(i).try_into().map_err(|_| FlowError::Overflow)?;
+            let c = c.checked_mul(n + 1).ok_or(FlowError::Overflow)?;
+            eps = eps.max(c.abs());
+            before.push(w);
+            cost.push(c);
+        }
+        let saved : Vec<T> = self.edges.iter().map(|e| e.weight.clone()).collect();
+        let flow = self.dinic(s, t);
+        match self.cost_scaling(&before, &cost, eps) {
+            Some((weights, res, h)) => {
+                for (e, w) in self.edges.iter_mut().zip(weights) {
+                    e.weight = w;
+                }
+                Ok((flow, res, self.residual_potentials(h)))
+            }
+            None => {
+                for (e, w) in self.edges.iter_mut().zip(saved) {
+                    e.weight = w;
+                }
+                Err(FlowError::Overflow)
+            }
+        }
+    }
+
+    // 在dinic求出的最大流上求最小费用循环流，只计算结果而不修改图，数值无法转换时返回None
+    //
+    // 返回(每条边新的剩余容量, 费用, 每个点的势)
+    fn cost_scaling(&self, before : &[i128], cost : &[i128], mut eps : i128) -> Option<(Vec<T>, E, Vec<E>)> {
+        let n = self.labels.len() as i128;
+        let mut cap = Vec::with_capacity(self.edges.len());
+        for e in &self.edges {
+            let w : i128 = e.weight.clone().try_into().ok()?;
+            cap.push(w);
+        }
+        let mut p = vec![0i128; n as usize];
+        while eps > 1 {
+            eps = (eps / ALPHA).max(1);
+            self.refine(&mut cap, cost, &mut p, eps);
+        }
+        let mut res = 0i128;
+        let mut weights = Vec::with_capacity(cap.len());
+        for (i, &c) in cap.iter().enumerate() {
+            if !self.edges[i].reversed {
+                res = res.checked_add((before[i] - c).checked_mul(cost[i] / (n + 1))?)?;
+            }
+            weights.push(T::try_from(c).ok()?);
+        }
+        let mut h = Vec::with_capacity(n as usize);
+        for x in p {
+            h.push(E::try_from(x.div_euclid(n + 1)).ok()?);
+        }
+        Some((weights, E::try_from(res).ok()?, h))
+    }
+}
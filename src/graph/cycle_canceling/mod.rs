@@ -0,0 +1,171 @@
+//! 负环的检测与消圈算法
+//!
+//! 在残量网络上用Bellman-Ford(队列优化)寻找负环，每找到一个负环就沿着它推满流量，
+//! 直到残量网络中没有负环为止，此时的流是同流量下费用最小的流。
+//!
+//! 负环通过前驱图判断：松弛过程中前驱图里出现的环一定是负环，
+//! 而只要残量网络中存在负环，前驱图中最终一定会出现环。
+//!
+//! 这里消去的是最先找到的负环，而不是平均费用最小的负环，因此不是强多项式的：
+//! 迭代次数与容量和费用的大小有关，费用或容量不是整数时可能不会结束。
+
+use super::Graph;
+use crate::error::FlowError;
+use core::ops::Add;
+use core::ops::Sub;
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+impl<L, T, E, M> Graph<L, T, E, M>
+    where
+        L : Clone + Hash + Eq,
+        E : Clone + Default + Add<Output = E> + Sub<Output = E> + PartialEq + PartialOrd,
+        T : Clone + Default + Add<Output = T> + Sub<Output = T> + PartialEq + PartialOrd,
+        M : crate::costtype::MulTE<T, E> {
+
+    // 在前驱图中寻找环，pre[x]为松弛x时使用的残量边，usize::MAX表示没有前驱
+    //
+    // forward为true时pre[x]是指向x的边，否则pre[x]是从x出发的边(从汇点反向松弛的情形)。
+    // 找到环时按照残量边的方向返回环上的边
+    pub(super) fn find_pre_cycle(&self, pre : &[usize], forward : bool) -> Option<Vec<usize>> {
+        let n = pre.len();
+        let parent = |x : usize| -> usize {
+            if pre[x] == usize::MAX {
+                usize::MAX
+            }
+            else if forward {
+                self.edges[pre[x]].from
+            }
+            else {
+                self.edges[pre[x]].to
+            }
+        };
+        // 0表示未访问，1表示在当前的链上，2表示已经处理完
+        let mut color = vec![0u8; n];
+        for i in 0..n {
+            let mut now = i;
+            while now != usize::MAX && color[now] == 0 {
+                color[now] = 1;
+                now = parent(now);
+            }
+            if now != usize::MAX && color[now] == 1 {
+                let mut cycle = vec![pre[now]];
+                let mut x = parent(now);
+                while x != now {
+                    cycle.push(pre[x]);
+                    x = parent(x);
+                }
+                if forward {
+                    cycle.reverse();
+                }
+                return Some(cycle);
+            }
+            let mut now = i;
+            while now != usize::MAX && color[now] == 1 {
+                color[now] = 2;
+                now = parent(now);
+            }
+        }
+        None
+    }
+
+    // 在整个残量网络中寻找一个负环，返回环上的边
+    fn find_negative_cycle(&self) -> Option<Vec<usize>> {
+        let n = self.labels.len();
+        let mut dist = vec![E::default(); n];
+        let mut pre = vec![usize::MAX; n];
+        let mut cnt = vec![0usize; n];
+        let mut inque = vec![true; n];
+        let mut q : VecDeque<usize> = (0..n).collect();
+        while let Some(now) = q.pop_front() {
+            inque[now] = false;
+            let mut e = self.first[now].next_edge;
+            while e != usize::MAX {
+                let x = self.edges[e].to;
+                if self.edges[e].weight != T::default() {
+                    let d = dist[now].clone() + self.arc_cost(e);
+                    if d < dist[x] {
+                        dist[x] = d;
+                        pre[x] = e;
+                        cnt[x] = cnt[now] + 1;
+                        if cnt[x].is_multiple_of(n) {
+                            if let Some(cycle) = self.find_pre_cycle(&pre, true) {
+                                return Some(cycle);
+                            }
+                        }
+                        if !inque[x] {
+                            inque[x] = true;
+                            q.push_back(x);
+                        }
+                    }
+                }
+                e = self.edges[e].next_edge;
+            }
+        }
+        None
+    }
+
+    // 原图中所有边上的流量乘以费用之和，不包括下界的部分
+    fn residual_cost(&self) -> E {
+        let mut res = E::default();
+        for e in &self.edges {
+            if !e.reversed {
+                res = res + M::mul(&self.edges[e.opp_edge].weight, &e.cost);
+            }
+        }
+        res
+    }
+
+    /// 在残量网络上不断消去负环，得到同流量下费用最小的流
+    ///
+    /// 不改变每个点的流量平衡，返回费用的变化量（非正数）。
+    /// 每次消去最先找到的负环，费用和容量为整数时一定会结束，否则可能不会结束。
+    ///
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2]);
+    /// g.add_edge2(0, 1, &2, &1).unwrap();
+    /// g.add_edge2(1, 2, &3, &-4).unwrap();
+    /// g.add_edge2(2, 0, &1, &1).unwrap();
+    /// assert_eq!(g.min_cost_circulation(), -2);
+    /// ```
+    pub fn min_cost_circulation(&mut self) -> E {
+        let before = self.residual_cost();
+        while let Some(cycle) = self.find_negative_cycle() {
+            let mut f = self.edges[cycle[0]].weight.clone();
+            for &e in &cycle {
+                if self.edges[e].weight < f {
+                    f = self.edges[e].weight.clone();
+                }
+            }
+            for &e in &cycle {
+                self.push_flow(e, f.clone());
+            }
+        }
+        self.residual_cost() - before
+    }
+
+    /// 使用消圈算法求从s到t的最小费用最大流
+    ///
+    /// 先用Dinic求出一个最大流，再消去残量网络中的所有负环，
+    /// 因此费用可以为负数，图中也可以有负环。返回(流量, 费用)，s或t不合法时返回错误。
+    /// 消圈的过程与min_cost_circulation相同，费用或容量不是整数时可能不会结束。
+    ///
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2, 3]);
+    /// g.add_edge2(0, 1, &1, &1).unwrap();
+    /// g.add_edge2(1, 3, &1, &1).unwrap();
+    /// g.add_edge2(1, 2, &2, &-3).unwrap();
+    /// g.add_edge2(2, 1, &2, &1).unwrap();
+    /// assert_eq!(g.mcmf_cycle_canceling(0, 3), Ok((1, -2)));
+    /// ```
+    pub fn mcmf_cycle_canceling(&mut self, s : usize, t : usize) -> Result<(T, E), FlowError> {
+        self.check_node(s)?;
+        self.check_node(t)?;
+        let before = self.residual_cost();
+        let flow = self.dinic(s, t);
+        self.min_cost_circulation();
+        Ok((flow, self.residual_cost() - before))
+    }
+}
@@ -0,0 +1,147 @@
+//! 将残量网络中的流分解为路径和环
+//!
+//! 每条边上的流量为下界加上反向边的残量，分解时每次沿着还有剩余流量的边走，
+//! 走到汇点就得到一条路径，走到已经在当前路径上的点就得到一个环，
+//! 并从经过的边上减去路径或环的流量。
+
+use super::Graph;
+use super::edge::EdgeId;
+use crate::error::FlowError;
+use core::ops::Add;
+use core::ops::Sub;
+use std::hash::Hash;
+
+/// 流分解中的一条路径或者一个环
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowPath<T, E> {
+    /// 依次经过的点，环的第一个点不会在末尾重复
+    pub nodes : Vec<usize>,
+    /// 依次经过的边
+    pub edges : Vec<EdgeId>,
+    /// 路径上的流量
+    pub flow : T,
+    /// 路径上的总费用，即每条边的费用与流量的乘积之和
+    pub cost : E
+}
+
+/// 流分解的结果
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowDecomposition<T, E> {
+    /// 从源点出发的路径，流量守恒时都以汇点结束
+    pub paths : Vec<FlowPath<T, E>>,
+    /// 剩余的环
+    pub cycles : Vec<FlowPath<T, E>>
+}
+
+impl<L, T, E, M> Graph<L, T, E, M>
+    where
+        L : Clone + Hash + Eq,
+        E : Clone + Default + Add<Output = E> + Sub<Output = E> + PartialEq + PartialOrd,
+        T : Clone + Default + Add<Output = T> + Sub<Output = T> + PartialEq + PartialOrd,
+        M : crate::costtype::MulTE<T, E> {
+
+    // 由经过的边和剩余流量构造路径，并从剩余流量中减去路径的流量
+    fn take_path(&self, edges : Vec<usize>, rest : &mut [T]) -> FlowPath<T, E> {
+        let mut flow = rest[edges[0]].clone();
+        for &e in &edges {
+            if rest[e] < flow {
+                flow = rest[e].clone();
+            }
+        }
+        let mut cost = E::default();
+        let mut nodes = Vec::with_capacity(edges.len() + 1);
+        for &e in &edges {
+            rest[e] = rest[e].clone() - flow.clone();
+            cost = cost + M::mul(&flow, &self.edges[e].cost);
+            nodes.push(self.edges[e].from);
+        }
+        FlowPath { nodes, edges : edges.into_iter().map(EdgeId).collect(), flow, cost }
+    }
+
+    // 从start出发沿着有剩余流量的边走，直到没有剩余流量的出边为止
+    fn walk_flow(&self, start : usize, t : usize, rest : &mut [T], current : &mut [usize],
+        pos : &mut [usize], res : &mut FlowDecomposition<T, E>) {
+        let mut stack = vec![start];
+        let mut path = vec![];
+        pos[start] = 0;
+        loop {
+            let now = *stack.last().unwrap();
+            let mut e = current[now];
+            while e != usize::MAX && (self.edges[e].reversed || rest[e] == T::default()) {
+                e = self.edges[e].next_edge;
+            }
+            current[now] = e;
+            if now == t || e == usize::MAX {
+                if !path.is_empty() {
+                    let mut p = self.take_path(path, rest);
+                    p.nodes.push(now);
+                    res.paths.push(p);
+                }
+                for x in stack {
+                    pos[x] = usize::MAX;
+                }
+                if now == start {
+                    return;
+                }
+                stack = vec![start];
+                path = vec![];
+                pos[start] = 0;
+                continue;
+            }
+            let x = self.edges[e].to;
+            path.push(e);
+            if pos[x] != usize::MAX {
+                let p = pos[x];
+                let cycle = self.take_path(path.split_off(p), rest);
+                for &y in &stack[p + 1..] {
+                    pos[y] = usize::MAX;
+                }
+                stack.truncate(p + 1);
+                res.cycles.push(cycle);
+            }
+            else {
+                pos[x] = stack.len();
+                stack.push(x);
+            }
+        }
+    }
+
+    /// 将当前残量网络中的流分解为从s到t的路径和若干个环
+    ///
+    /// 每条边上的流量包含了下界的部分。流量在除s和t之外的点都守恒时，
+    /// 所有路径都以t结束；否则路径会在没有剩余流量的出边的点结束。s或t不合法时返回错误。
+    ///
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2]);
+    /// g.add_edge2(0, 1, &2, &1).unwrap();
+    /// g.add_edge2(1, 2, &1, &3).unwrap();
+    /// g.add_edge2(0, 2, &1, &5).unwrap();
+    /// g.mcmf(0, 2).unwrap();
+    /// let d = g.decompose_flow(0, 2).unwrap();
+    /// assert_eq!(d.paths.len(), 2);
+    /// assert_eq!(d.paths[0].nodes, vec![0, 2]);
+    /// assert_eq!((d.paths[1].flow, d.paths[1].cost), (1, 4));
+    /// assert!(d.cycles.is_empty());
+    /// ```
+    pub fn decompose_flow(&self, s : usize, t : usize) -> Result<FlowDecomposition<T, E>, FlowError> {
+        self.check_node(s)?;
+        self.check_node(t)?;
+        let n = self.labels.len();
+        let mut rest : Vec<T> = (0..self.edges.len()).map(|e| {
+            if self.edges[e].reversed { T::default() } else { self.edge_flow(e) }
+        }).collect();
+        let mut current : Vec<usize> = self.first.iter().map(|x| x.next_edge).collect();
+        let mut pos = vec![usize::MAX; n];
+        let mut res = FlowDecomposition { paths : vec![], cycles : vec![] };
+        if s != t {
+            self.walk_flow(s, t, &mut rest, &mut current, &mut pos, &mut res);
+        }
+        for i in 0..n {
+            self.walk_flow(i, usize::MAX, &mut rest, &mut current, &mut pos, &mut res);
+        }
+        Ok(res)
+    }
+}
@@ -0,0 +1,378 @@
+//! 图与DOT格式之间的转换
+//!
+//! 每个点输出为以编号为id的点，`label`属性为点的标签，供给和需求不为0时输出`supply`和`demand`属性；
+//! 每条边输出`capacity`、`flow`、`cost`属性，下界不为0时输出`lower`属性，
+//! 另外输出`"流量/容量,费用"`形式的`label`属性以便于显示。
+//!
+//! 读入时属性的顺序任意，未知的属性会被忽略。点通过id查找，只出现在边中的点会被自动添加，
+//! 没有`label`属性的点以id作为标签。子图中的点和边会被展开，以子图为端点的边会连接子图中的每个点，
+//! `node [...]`和`edge [...]`设置的默认属性对其之后同一作用域中的点和边生效。
+//! 无向图中的每条边被看作两条方向相反、容量和费用相同的边，流量属于从前一个点指向后一个点的边。
+
+use super::Graph;
+use crate::error::FlowError;
+use crate::io::{add_text_node, read_text, StrIO, TextEdge};
+use core::ops::Add;
+use core::ops::Sub;
+use dot_structures::{Attribute, EdgeTy, GraphAttributes, Id, NodeId, Stmt, Vertex};
+use graphviz_rust_bla::parse;
+use graphviz_rust_bla::printer::{DotPrinter, PrinterContext};
+use std::collections::HashMap;
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{Read, Write};
+
+/// 输出DOT格式时的显示选项
+///
+/// 默认不添加任何显示效果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DotOptions {
+    /// 将从该点出发的最小割的源点一侧(即`get_cut`的结果)放在名为`cluster_source`的子图中，
+    /// 并将从源点一侧指向另一侧的边标为红色加粗
+    pub cut_source : Option<usize>,
+    /// 将满流的边标为红色
+    pub highlight_saturated : bool,
+    /// 不输出流量为0的边
+    pub hide_zero_flow : bool
+}
+
+// 带引号的id，对其中的特殊字符转义
+fn quoted(s : &str) -> Id {
+    let mut res = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' | '\\' => { res.push('\\'); res.push(c); },
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            _ => res.push(c)
+        }
+    }
+    res.push('"');
+    Id::Escaped(res)
+}
+
+fn unescape(s : &str) -> Result<String, FlowError> {
+    let mut res = String::new();
+    let mut it = s.chars();
+    while let Some(c) = it.next() {
+        if c != '\\' {
+            res.push(c);
+            continue;
+        }
+        match it.next() {
+            Some('n') => res.push('\n'),
+            Some('r') => res.push('\r'),
+            Some('t') => res.push('\t'),
+            Some('b') => res.push('\u{8}'),
+            Some('f') => res.push('\u{c}'),
+            Some('u') => {
+                let code : String = it.by_ref().take(4).collect();
+                let c = u32::from_str_radix(&code, 16).ok().and_then(char::from_u32)
+                    .ok_or_else(|| FlowError::Parse(format!("\\u{}", code)))?;
+                res.push(c);
+            },
+            Some(c) => res.push(c),
+            None => res.push('\\')
+        }
+    }
+    Ok(res)
+}
+
+// 解析器不接受字符串中没有转义的单引号，读入前在字符串中的单引号前加上反斜杠，跳过注释
+fn escape_apostrophes(text : &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    let mut it = text.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = it.next() {
+        res.push(c);
+        let end = match (in_string, c, it.peek().copied()) {
+            (true, '\\', _) => {
+                res.extend(it.next());
+                continue;
+            },
+            (true, '\'', _) => {
+                res.pop();
+                res.push_str("\\'");
+                continue;
+            },
+            (_, '"', _) => {
+                in_string = !in_string;
+                continue;
+            },
+            (false, '#', _) | (false, '/', Some('/')) => "\n",
+            (false, '/', Some('*')) => {
+                res.extend(it.next());
+                "*/"
+            },
+            _ => continue
+        };
+        // 原样复制注释直到结束标记
+        let start = res.len();
+        while !res[start..].ends_with(end) {
+            match it.next() {
+                Some(c) => res.push(c),
+                None => break
+            }
+        }
+    }
+    res
+}
+
+// id的文本内容，去掉引号或者尖括号
+fn id_text(x : &Id) -> Result<String, FlowError> {
+    match x {
+        Id::Escaped(s) => unescape(s.strip_prefix('"').and_then(|x| x.strip_suffix('"')).unwrap_or(s)),
+        Id::Html(s) => Ok(s.strip_prefix('<').and_then(|x| x.strip_suffix('>')).unwrap_or(s).to_string()),
+        Id::Plain(s) | Id::Anonymous(s) => Ok(s.clone())
+    }
+}
+
+type Attrs = Vec<(String, String)>;
+
+fn attrs(a : &[Attribute]) -> Result<Attrs, FlowError> {
+    a.iter().map(|x| Ok((id_text(&x.0)?, id_text(&x.1)?))).collect()
+}
+
+// 属性key的值，出现多次时以最后一次为准
+fn get<'a>(a : &'a Attrs, key : &str) -> Option<&'a str> {
+    a.iter().rev().find(|x| x.0 == key).map(|x| x.1.as_str())
+}
+
+// 展开DOT文件中的语句后得到的点和边
+#[derive(Default)]
+struct DotReader {
+    ids : HashMap<String, usize>,
+    nodes : Vec<(String, Attrs)>,
+    edges : Vec<(usize, usize, Attrs)>
+}
+
+impl DotReader {
+    fn node(&mut self, id : &NodeId, defaults : &Attrs) -> Result<usize, FlowError> {
+        if id.1.is_some() {
+            return Err(FlowError::UnsupportedGraph(String::from("node port")));
+        }
+        let name = id_text(&id.0)?;
+        if let Some(&x) = self.ids.get(&name) {
+            return Ok(x);
+        }
+        self.ids.insert(name.clone(), self.nodes.len());
+        self.nodes.push((name, defaults.clone()));
+        Ok(self.nodes.len() - 1)
+    }
+
+    fn vertex(&mut self, v : &Vertex, node_defaults : &Attrs, edge_defaults : &Attrs) -> Result<Vec<usize>, FlowError> {
+        match v {
+            Vertex::N(id) => Ok(vec![self.node(id, node_defaults)?]),
+            Vertex::S(s) => self.stmts(&s.stmts, node_defaults.clone(), edge_defaults.clone())
+        }
+    }
+
+    // 展开一个作用域中的语句，返回其中出现的点
+    fn stmts(&mut self, stmts : &[Stmt], mut node_defaults : Attrs, mut edge_defaults : Attrs) -> Result<Vec<usize>, FlowError> {
+        let mut res = vec![];
+        for stmt in stmts {
+            match stmt {
+                Stmt::Node(node) => {
+                    let x = self.node(&node.id, &node_defaults)?;
+                    self.nodes[x].1.extend(attrs(&node.attributes)?);
+                    res.push(x);
+                },
+                Stmt::Edge(edge) => {
+                    let vertices = match &edge.ty {
+                        EdgeTy::Pair(u, v) => vec![u, v],
+                        EdgeTy::Chain(v) => v.iter().collect()
+                    };
+                    let mut a = edge_defaults.clone();
+                    a.extend(attrs(&edge.attributes)?);
+                    let mut last : Vec<usize> = vec![];
+                    for v in vertices {
+                        let now = self.vertex(v, &node_defaults, &edge_defaults)?;
+                        for &x in &last {
+                            for &y in &now {
+                                self.edges.push((x, y, a.clone()));
+                            }
+                        }
+                        res.extend(&now);
+                        last = now;
+                    }
+                },
+                Stmt::Subgraph(s) => {
+                    let x = self.stmts(&s.stmts, node_defaults.clone(), edge_defaults.clone())?;
+                    res.extend(x);
+                },
+                Stmt::GAttribute(GraphAttributes::Node(a)) => node_defaults.extend(attrs(a)?),
+                Stmt::GAttribute(GraphAttributes::Edge(a)) => edge_defaults.extend(attrs(a)?),
+                Stmt::GAttribute(GraphAttributes::Graph(_)) | Stmt::Attribute(_) => ()
+            }
+        }
+        Ok(res)
+    }
+}
+
+// 读取旧版本输出的"流量/容量,费用"形式的边的label
+fn legacy_label(label : &str) -> Result<(&str, &str, &str), FlowError> {
+    let bad = |s : &str| FlowError::MalformedFile(String::from(s));
+    let (flow, rest) = label.split_once('/').ok_or_else(|| bad("edge without capacity"))?;
+    let (cap, cost) = rest.split_once(',').ok_or_else(|| bad("edge without capacity"))?;
+    Ok((flow, cap, cost))
+}
+
+impl<L, T, E, M> Graph<L, T, E, M>
+    where
+        L : StrIO + Clone + Hash + Eq,
+        E : StrIO + Clone + Default,
+        T : StrIO + Clone + Default + Add<Output = T> + Sub<Output = T> + PartialOrd,
+        M : super::super::costtype::MulTE<T, E> {
+    // 转换为DOT中的有向图
+    fn to_dot_graph(&self, options : &DotOptions) -> Result<dot_structures::Graph, FlowError> {
+        use dot_generator::*;
+        use dot_structures::*;
+        let mut g = graph!(di id!("network"));
+        for (i, l) in self.labels.iter().enumerate() {
+            let mut a = vec![Attribute(id!("label"), quoted(&l.to_str()))];
+            let (supply, demand) = (self.node_supply(i), self.node_demand(i));
+            if supply != T::default() {
+                a.push(Attribute(id!("supply"), quoted(&supply.to_str())));
+            }
+            if demand != T::default() {
+                a.push(Attribute(id!("demand"), quoted(&demand.to_str())));
+            }
+            g.add_stmt(Stmt::Node(Node::new(node_id!(i), a)));
+        }
+        let mut source_side = vec![false; self.labels.len()];
+        if let Some(s) = options.cut_source {
+            let cut = self.get_cut(s)?;
+            let mut stmts = vec![
+                Stmt::Attribute(Attribute(id!("label"), quoted("source side"))),
+                Stmt::Attribute(Attribute(id!("color"), id!("blue")))
+            ];
+            for &x in &cut {
+                source_side[x] = true;
+                stmts.push(Stmt::Node(Node::new(node_id!(x), vec![])));
+            }
+            g.add_stmt(Stmt::Subgraph(Subgraph { id : id!("cluster_source"), stmts }));
+        }
+        for (id, from, to, flow) in self.edges_with_flow() {
+            if options.hide_zero_flow && flow == T::default() {
+                continue;
+            }
+            let (cap, cost, lower) = (self.edge_capacity(id.0), self.edges[id.0].cost.clone(), self.edges[id.0].lower.clone());
+            let label = format!("{}/{},{}", flow.to_str(), cap.to_str(), cost.to_str());
+            let mut a = vec![
+                Attribute(id!("capacity"), quoted(&cap.to_str())),
+                Attribute(id!("flow"), quoted(&flow.to_str())),
+                Attribute(id!("cost"), quoted(&cost.to_str()))
+            ];
+            if lower != T::default() {
+                a.push(Attribute(id!("lower"), quoted(&lower.to_str())));
+            }
+            a.push(Attribute(id!("label"), quoted(&label)));
+            let cut = source_side[from] && !source_side[to];
+            if cut || (options.highlight_saturated && self.edges[id.index()].is_full()) {
+                a.push(Attribute(id!("color"), id!("red")));
+            }
+            if cut {
+                a.push(Attribute(id!("style"), id!("bold")));
+            }
+            g.add_stmt(stmt!(edge!(node_id!(from) => node_id!(to), a)));
+        }
+        Ok(g)
+    }
+
+    /// 将图以DOT格式写入w中，格式见`output_to_dot`
+    pub fn write_dot<W : Write>(&self, w : W) -> Result<(), FlowError> {
+        self.write_dot_with(w, &DotOptions::default())
+    }
+
+    /// 按照options中的显示选项将图以DOT格式写入w中
+    ///
+    /// 设置了cut_source时cut_source不合法会返回错误。隐藏的边不会被输出，因此读入后的图中也不包含这些边。
+    ///
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// use network_flow::graph::dot::DotOptions;
+    /// let mut g = Graph::<usize, u32, u32>::create_graph(&[0, 1, 2]);
+    /// g.add_edge(0, 1, &1).unwrap();
+    /// g.add_edge(1, 2, &5).unwrap();
+    /// g.add_edge(0, 2, &0).unwrap();
+    /// g.get_max_flow(0, 2).unwrap();
+    /// let options = DotOptions { cut_source : Some(0), highlight_saturated : true, hide_zero_flow : true };
+    /// let mut buf = vec![];
+    /// g.write_dot_with(&mut buf, &options).unwrap();
+    /// let s = String::from_utf8(buf).unwrap();
+    /// assert!(s.contains("subgraph cluster_source"));
+    /// assert_eq!(s.matches("color=red").count(), 1);
+    /// assert_eq!(s.matches("->").count(), 2);
+    /// ```
+    pub fn write_dot_with<W : Write>(&self, mut w : W, options : &DotOptions) -> Result<(), FlowError> {
+        let mut ctx = PrinterContext::default();
+        w.write_all(self.to_dot_graph(options)?.print(&mut ctx).as_bytes())?;
+        w.flush()?;
+        Ok(())
+    }
+
+    /// 将图输出到.dot文件中
+    ///
+    /// 每个点的id为其编号，`label`属性为标签，供给和需求不为0时输出`supply`和`demand`属性；
+    /// 每条边输出`capacity`、`flow`和`cost`属性，下界不为0时输出`lower`属性，
+    /// 以及用于显示的`"流量/容量,费用"`形式的`label`属性。
+    pub fn output_to_dot(&self, file : &str) -> Result<(), FlowError> {
+        self.write_dot(File::create(file)?)
+    }
+
+    /// 按照options中的显示选项将图输出到.dot文件中，见`write_dot_with`
+    pub fn output_to_dot_with(&self, file : &str, options : &DotOptions) -> Result<(), FlowError> {
+        self.write_dot_with(File::create(file)?, options)
+    }
+
+    /// 从r中读取DOT格式的图，支持的内容见`from_dot`
+    ///
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// let s = "graph { edge [cost=2]; s -- a [capacity=3]; t -- subgraph { a; b } [capacity=1] }";
+    /// let mut g = Graph::<String, u32, i32>::read_dot(s.as_bytes()).unwrap();
+    /// assert_eq!(g.get_index(&String::from("t")), Some(2));
+    /// assert_eq!(g.mcmf(0, 2), Ok((1, 4)));
+    /// ```
+    pub fn read_dot<R : Read>(r : R) -> Result<Self, FlowError> {
+        let text = read_text(r)?;
+        let (directed, stmts) = match parse(&escape_apostrophes(&text)).map_err(FlowError::Parse)? {
+            dot_structures::Graph::DiGraph { strict : true, .. } | dot_structures::Graph::Graph { strict : true, .. } =>
+                return Err(FlowError::UnsupportedGraph(String::from("strict graph"))),
+            dot_structures::Graph::DiGraph { stmts, .. } => (true, stmts),
+            dot_structures::Graph::Graph { stmts, .. } => (false, stmts)
+        };
+        let mut reader = DotReader::default();
+        reader.stmts(&stmts, vec![], vec![])?;
+        let mut res = Self::new();
+        for (name, a) in &reader.nodes {
+            add_text_node(&mut res, get(a, "label").unwrap_or(name), get(a, "supply"), get(a, "demand"))?;
+        }
+        for (from, to, a) in &reader.edges {
+            let edge = match (get(a, "capacity"), get(a, "label")) {
+                (None, Some(label)) => {
+                    let (flow, capacity, cost) = legacy_label(label)?;
+                    TextEdge { capacity : Some(capacity), lower : get(a, "lower"), cost : Some(cost), flow : Some(flow) }
+                },
+                (capacity, _) => TextEdge { capacity, lower : get(a, "lower"), cost : get(a, "cost"), flow : get(a, "flow") }
+            };
+            edge.add_to(&mut res, *from, *to, directed)?;
+        }
+        Ok(res)
+    }
+
+    /// 从.dot文件中读取图
+    ///
+    /// 点通过id查找，以`label`属性(缺省为id)作为标签，可以有`supply`和`demand`属性；
+    /// 边必须有`capacity`属性，`flow`、`cost`和`lower`属性缺省为下界、0和0，
+    /// 也可以读取旧版本输出的`"流量/容量,费用"`形式的`label`。属性的顺序任意，其他属性会被忽略。
+    ///
+    /// 支持子图、以子图为端点的边、连续的边、`node`和`edge`的默认属性以及无向图，
+    /// 无向图中的每条边被看作两条方向相反的边。
+    /// 文件无法解析时返回FlowError::Parse，使用了点的端口或者是strict图时返回FlowError::UnsupportedGraph，
+    /// 标签重复、边缺少容量或者流量不在下界和容量之间时返回FlowError::MalformedFile。
+    pub fn from_dot(file : &str) -> Result<Self, FlowError> {
+        Self::read_dot(File::open(file)?)
+    }
+}
@@ -1,17 +1,34 @@
 //! 存储图中边的信息的数据结构
 
+/// 图中一条边的编号，由add_edge等函数返回
+/// 
+/// 指向添加的正向边，可以用于查询边上的容量、流量和费用
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EdgeId(pub(crate) usize);
+
+impl EdgeId {
+    /// 边在Graph::edges中的下标，对应的反向边的下标为index() + 1
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
 /// T 为边上容量的类型
 /// 
 /// E 为边上费用的类型
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Edge<T, E> {
     pub(crate) from : usize,
     pub(crate) to : usize,
     pub(crate) next_edge : usize,
     pub(crate) opp_edge : usize,
     pub(crate) weight : T,
+    pub(crate) capacity : T,
     pub(crate) cost : E,
-    pub(crate) reversed : bool
+    pub(crate) reversed : bool,
+    pub(crate) lower : T
 }
 
 impl<T, E> Edge<T, E> 
@@ -25,15 +42,17 @@ impl<T, E> Edge<T, E>
             next_edge : usize::MAX,
             opp_edge : usize::MAX,
             weight : T::default(),
+            capacity : T::default(),
             cost : E::default(),
-            reversed : false
+            reversed : false,
+            lower : T::default()
         }
     }
 
     pub(crate) fn create_edge(from : usize, to : usize, next_edge : usize, opp_edge : usize, 
         weight : T, cost : E) -> Edge<T, E> {
         Edge::<T, E> {
-            from, to, next_edge, opp_edge, weight, cost, reversed : false
+            from, to, next_edge, opp_edge, weight, capacity : T::default(), cost, reversed : false, lower : T::default()
         }
     }
 
@@ -1,19 +1,50 @@
 //! 用于建立存储图的数据结构的module
 pub mod edge;
+mod push_relabel;
+mod primal_dual;
+mod network_simplex;
+mod cost_scaling;
+mod bounded;
+mod cycle_canceling;
+pub mod decomposition;
+pub mod snapshot;
+mod binary;
+mod update;
+pub mod dot;
+#[cfg(feature = "serde")]
+mod serialize;
 
 use edge::*;
+use crate::error::FlowError;
 use core::ops::Add;
 use core::ops::Sub;
-use core::mem::size_of;
 use std::collections::VecDeque;
 use std::collections::HashMap;
 use std::hash::Hash;
-use std::io::Read;
-use std::io::Write;
 // use std::collections::HashMap;
 use std::marker::PhantomData;
-use std::fs::File;
-use std::io::Error;
+
+/// 求最大流时可以选择的算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MaxFlowAlgorithm {
+    /// Dinic算法，`get_max_flow`默认使用该算法
+    Dinic,
+    /// 最高标号预流推进算法，使用了gap优化和全局重标号优化，适用于稠密图
+    PushRelabel
+}
+
+/// 求最小费用最大流时可以选择的算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum McmfAlgorithm {
+    /// 每轮使用spfa求最短路，`mcmf`默认使用该算法
+    Spfa,
+    /// 原始对偶算法，只在开始时用spfa求一次势，之后每轮在约化费用上使用Dijkstra
+    PrimalDual,
+    /// 先求最大流再消去残量网络中的负环，可以处理有负环的图
+    CycleCanceling
+}
 
 /// 存储图的数据结构
 /// 
@@ -30,7 +61,9 @@ pub struct Graph<L : Hash, T, E = (), M : super::costtype::MulTE<T, E> = super::
     pub edges : Vec<Edge<T, E>>,
     first : Vec<Edge<T, E>>,
     m : PhantomData<M>,
-    hs : HashMap<L, usize>
+    hs : HashMap<L, usize>,
+    supply : Vec<T>,
+    demand : Vec<T>
 }
 
 fn copy_nodes<L : Clone>(nodes : &[L]) -> Vec<L> {
@@ -51,12 +84,101 @@ fn empty_edges<T : Default, E : Default>(len : usize) -> Vec<Edge<T, E>> {
 
 fn make_hash<L : Clone + Hash + Eq>(nodes : &[L]) -> HashMap<L, usize> {
     let mut res = HashMap::new();
-    for i in 0..nodes.len() {
-        res.insert(nodes[i].clone(), i);
+    for (i, node) in nodes.iter().enumerate() {
+        res.insert(node.clone(), i);
     }
     res
 }
 
+impl<L : Hash, T, E, M : super::costtype::MulTE<T, E>> Graph<L, T, E, M> {
+    // 检查点的编号是否合法
+    fn check_node(&self, index : usize) -> Result<(), FlowError> {
+        if index < self.labels.len() {
+            Ok(())
+        }
+        else {
+            Err(FlowError::InvalidNode(index))
+        }
+    }
+
+    /// 图中点的数量
+    pub fn node_count(&self) -> usize {
+        self.labels.len()
+    }
+
+    // 检查边的编号是否为图中的正向边
+    fn check_edge(&self, id : EdgeId) -> Result<(), FlowError> {
+        if id.0 < self.edges.len() && !self.edges[id.0].reversed {
+            Ok(())
+        }
+        else {
+            Err(FlowError::InvalidEdge(id.0))
+        }
+    }
+
+    // 将边e从其起点的链表中去掉，自环的正向边不在链表中
+    fn unlink(&mut self, e : usize) {
+        let node = self.edges[e].from;
+        let next = self.edges[e].next_edge;
+        if self.first[node].next_edge == e {
+            self.first[node].next_edge = next;
+            return;
+        }
+        let mut now = self.first[node].next_edge;
+        while now != usize::MAX {
+            if self.edges[now].next_edge == e {
+                self.edges[now].next_edge = next;
+                return;
+            }
+            now = self.edges[now].next_edge;
+        }
+    }
+
+    // 将边e加入其起点的链表的开头，与unlink相对
+    fn link(&mut self, e : usize) {
+        let node = self.edges[e].from;
+        self.edges[e].next_edge = self.first[node].next_edge;
+        self.first[node].next_edge = e;
+    }
+
+    // 被删除的边的正向边和反向边都被标记为反向边，且不在任何链表中
+    fn is_removed(&self, e : usize) -> bool {
+        self.edges[e].reversed && self.edges[self.edges[e].opp_edge].reversed
+    }
+
+    /// 删除一条边及其反向边
+    /// 
+    /// 其他边的编号不变，被删除的边的编号也不会再分配给之后添加的边，
+    /// 之后使用这个编号查询或修改边时返回FlowError::InvalidEdge。
+    /// 边上的流量随之消失，之后其端点上的流量可能不再守恒。
+    /// id不是图中正向边的编号时返回FlowError::InvalidEdge
+    /// 
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// use network_flow::error::FlowError;
+    /// let mut g = Graph::<usize, i32>::create_graph(&[0, 1, 2]);
+    /// let a = g.add_edge(0, 1, &3).unwrap();
+    /// g.add_edge(1, 2, &2).unwrap();
+    /// let c = g.add_edge(0, 2, &4).unwrap();
+    /// g.remove_edge(a).unwrap();
+    /// assert_eq!(g.get_endpoints(a), Err(FlowError::InvalidEdge(a.index())));
+    /// assert_eq!(g.get_endpoints(c), Ok((0, 2)));
+    /// assert!(g.remove_edge(a).is_err());
+    /// assert_eq!(g.get_max_flow(0, 2), Ok(4));
+    /// ```
+    pub fn remove_edge(&mut self, id : EdgeId) -> Result<(), FlowError> {
+        self.check_edge(id)?;
+        let i = id.0;
+        self.unlink(i);
+        self.unlink(i + 1);
+        for e in [i, i + 1] {
+            self.edges[e].reversed = true;
+            self.edges[e].next_edge = usize::MAX;
+        }
+        Ok(())
+    }
+}
+
 impl<L, T, E, M> Graph<L, T, E, M> 
     where 
         L : Clone + Hash + Eq,
@@ -66,7 +188,7 @@ impl<L, T, E, M> Graph<L, T, E, M>
 
     /// 获得某一个label对应的编号
     pub fn get_index(&self, label : &L) -> Option<usize> {
-        self.hs.get(label).map(|x| *x)
+        self.hs.get(label).copied()
     }
 
     /// 获得某一个编号对应的label
@@ -85,6 +207,8 @@ impl<L, T, E, M> Graph<L, T, E, M>
         self.labels = vec![];
         self.first = vec![];
         self.hs.clear();
+        self.supply = vec![];
+        self.demand = vec![];
     }
 
     /// 创建一个初始为空的图
@@ -94,7 +218,9 @@ impl<L, T, E, M> Graph<L, T, E, M>
             edges : vec![],
             first : vec![],
             m : PhantomData,
-            hs : HashMap::<L, usize>::new()
+            hs : HashMap::<L, usize>::new(),
+            supply : vec![],
+            demand : vec![]
         }
     }
 
@@ -105,7 +231,9 @@ impl<L, T, E, M> Graph<L, T, E, M>
             edges : vec![],
             first : empty_edges(nodes.len()),
             m : PhantomData,
-            hs : make_hash(nodes)
+            hs : make_hash(nodes),
+            supply : vec![],
+            demand : vec![]
         }
     }
 
@@ -116,13 +244,82 @@ impl<L, T, E, M> Graph<L, T, E, M>
         self.hs.insert(label.clone(), self.labels.len() - 1);
     }
 
-    /// 获得从index指出的第一条边
+    /// 删除一个点以及与其相连的所有边，返回其标签和被移动到index的点原来的编号
+    /// 
+    /// 编号最大的点会被移动到被删除的点的位置，其标签对应的编号随之改变，
+    /// 被删除的点就是编号最大的点时不移动任何点，返回None。
+    /// 相连的边按照remove_edge的方式删除，其他边的编号不变。
+    /// 
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// let mut g = Graph::<&str, i32>::new();
+    /// g.add_edge_by_label(&"s", &"a", &3);
+    /// g.add_edge_by_label(&"a", &"t", &2);
+    /// let e = g.add_edge_by_label(&"s", &"t", &1);
+    /// assert_eq!(g.remove_node(1), Ok(("a", Some(2))));
+    /// assert_eq!(g.get_index(&"t"), Some(1));
+    /// assert_eq!(g.get_index(&"a"), None);
+    /// assert_eq!(g.get_endpoints(e), Ok((0, 1)));
+    /// assert_eq!(g.get_max_flow_by_label(&"s", &"t"), Ok(1));
+    /// assert_eq!(g.remove_node(1), Ok(("t", None)));
+    /// ```
+    pub fn remove_node(&mut self, index : usize) -> Result<(L, Option<usize>), FlowError> {
+        self.check_node(index)?;
+        while let Some(e) = self.first_edge(index) {
+            let id = if e.reversed { e.opp_edge } else { self.first[index].next_edge };
+            self.remove_edge(EdgeId(id))?;
+            // 端点已经不存在，这样的边无法再通过restore恢复
+            for x in [id, id + 1] {
+                self.edges[x].from = usize::MAX;
+                self.edges[x].to = usize::MAX;
+            }
+        }
+        let last = self.labels.len() - 1;
+        let label = self.labels.swap_remove(index);
+        self.first.swap_remove(index);
+        if self.hs.get(&label) == Some(&index) {
+            self.hs.remove(&label);
+        }
+        for values in [&mut self.supply, &mut self.demand] {
+            if values.len() > last {
+                values.swap_remove(index);
+            }
+            else if values.len() > index {
+                values[index] = T::default();
+            }
+        }
+        if index != last {
+            if self.hs.get(&self.labels[index]) == Some(&last) {
+                self.hs.insert(self.labels[index].clone(), index);
+            }
+            self.first[index].from = index;
+            self.first[index].to = index;
+            // 自环的正向边不在链表中，通过反向边修改
+            let mut e = self.first[index].next_edge;
+            while e != usize::MAX {
+                for x in [e, self.edges[e].opp_edge] {
+                    let edge = &mut self.edges[x];
+                    if edge.from == last {
+                        edge.from = index;
+                    }
+                    if edge.to == last {
+                        edge.to = index;
+                    }
+                }
+                e = self.edges[e].next_edge;
+            }
+        }
+        Ok((label, if index != last { Some(last) } else { None }))
+    }
+
+    /// 获得从index指出的第一条边，index不合法时也返回None
     pub fn first_edge(&self, index : usize) -> Option<&Edge<T, E>> {
-        if self.first[index].next_edge == usize::MAX {
+        let e = self.first.get(index)?.next_edge;
+        if e == usize::MAX {
             None
         }
         else {
-            Some(&self.edges[self.first[index].next_edge])
+            Some(&self.edges[e])
         }
     }
 
@@ -168,7 +365,13 @@ impl<L, T, E, M> Graph<L, T, E, M>
     }
 
     /// 使用first_edge和next_edge函数得到从index出发的所有边及其编号
-    pub fn get_all_edges(&self, index : usize) -> Vec<(&Edge<T, E>, usize)> {
+    #[allow(clippy::type_complexity)]
+    pub fn get_all_edges(&self, index : usize) -> Result<Vec<(&Edge<T, E>, usize)>, FlowError> {
+        self.check_node(index)?;
+        Ok(self.all_edges(index))
+    }
+
+    fn all_edges(&self, index : usize) -> Vec<(&Edge<T, E>, usize)> {
         let mut res = vec![];
         let mut temp = self.first_edge(index);
         let mut no = self.first[index].next_edge;
@@ -181,46 +384,149 @@ impl<L, T, E, M> Graph<L, T, E, M>
     }
 
     /// 获得与index相邻的所有点
-    pub fn get_neighbor(&self, index : usize) -> Vec<usize> {
+    pub fn get_neighbor(&self, index : usize) -> Result<Vec<usize>, FlowError> {
+        self.check_node(index)?;
         let mut res = vec![];
         let mut edge = self.first_edge(index);
         while let Some(x) = edge {
             res.push(x.to);
             edge = self.next_edge(x);
         }
-        res
+        Ok(res)
     }
 
 }
 
-impl<L, T, E, M> Graph<L, T, E, M> 
+impl<L, T, E, M> Graph<L, T, E, M>
+    where
+        L : Clone + Hash + Eq,
+        E : Default,
+        T : Clone + Default,
+        M : super::costtype::MulTE<T, E> {
+
+    /// 设置编号为index的点的供给量，用于min_cost_flow
+    pub fn set_supply(&mut self, index : usize, supply : &T) -> Result<(), FlowError> {
+        self.check_node(index)?;
+        if self.supply.len() < self.labels.len() {
+            self.supply.resize(self.labels.len(), T::default());
+        }
+        self.supply[index] = supply.clone();
+        Ok(())
+    }
+
+    /// 设置编号为index的点的需求量，用于min_cost_flow
+    pub fn set_demand(&mut self, index : usize, demand : &T) -> Result<(), FlowError> {
+        self.check_node(index)?;
+        if self.demand.len() < self.labels.len() {
+            self.demand.resize(self.labels.len(), T::default());
+        }
+        self.demand[index] = demand.clone();
+        Ok(())
+    }
+
+    /// 设置标签为label的点的供给量，如果这个点不存在则先添加这个点
+    pub fn set_supply_by_label(&mut self, label : &L, supply : &T) -> Result<(), FlowError> {
+        let index = self.get_or_add_node(label);
+        self.set_supply(index, supply)
+    }
+
+    /// 设置标签为label的点的需求量，如果这个点不存在则先添加这个点
+    pub fn set_demand_by_label(&mut self, label : &L, demand : &T) -> Result<(), FlowError> {
+        let index = self.get_or_add_node(label);
+        self.set_demand(index, demand)
+    }
+
+    /// 获得编号为index的点的供给量，没有设置过时为0
+    pub fn get_supply(&self, index : usize) -> Result<T, FlowError> {
+        self.check_node(index)?;
+        Ok(self.node_supply(index))
+    }
+
+    /// 获得编号为index的点的需求量，没有设置过时为0
+    pub fn get_demand(&self, index : usize) -> Result<T, FlowError> {
+        self.check_node(index)?;
+        Ok(self.node_demand(index))
+    }
+
+    // 点x的供给量和需求量，x需要是合法的编号
+    pub(crate) fn node_supply(&self, x : usize) -> T {
+        self.supply.get(x).cloned().unwrap_or_default()
+    }
+
+    pub(crate) fn node_demand(&self, x : usize) -> T {
+        self.demand.get(x).cloned().unwrap_or_default()
+    }
+
+    fn get_or_add_node(&mut self, label : &L) -> usize {
+        match self.get_index(label) {
+            Some(x) => x,
+            None => {
+                self.add_node(label);
+                self.labels.len() - 1
+            }
+        }
+    }
+}
+
+impl<L, T, E, M> Default for Graph<L, T, E, M>
+    where
+        L : Clone + Hash + Eq,
+        E : Default,
+        T : Default,
+        M : super::costtype::MulTE<T, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 复制整个图，包括标签、边上当前的流量以及点的供给和需求
+impl<L, T, E, M> Clone for Graph<L, T, E, M>
+    where
+        L : Clone + Hash,
+        E : Clone,
+        T : Clone,
+        M : super::costtype::MulTE<T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            labels : self.labels.clone(),
+            edges : self.edges.clone(),
+            first : self.first.clone(),
+            m : PhantomData,
+            hs : self.hs.clone(),
+            supply : self.supply.clone(),
+            demand : self.demand.clone()
+        }
+    }
+}
+
+impl<L, T, E, M> Graph<L, T, E, M>
     where
         L : Hash,
         E : Clone + Default,
         T : Clone + Default,
         M : super::costtype::MulTE<T, E> {
 
-    /// 添加一条从from到to的边，容量为weight，费用为默认值
-    pub fn add_edge(&mut self, from : usize, to : usize, weight : &T) {
-        let mut edge = Edge::create_edge(
-            from, to, self.first[from].next_edge, 0, weight.clone(), E::default());
-        let mut edge2 = Edge::create_edge(
-            to, from, self.first[to].next_edge, 0, T::default(), E::default());
-        edge.opp_edge = self.edges.len() + 1;
-        edge2.opp_edge = self.edges.len();
-        edge2.reversed = true;
-        self.first[from].next_edge = self.edges.len();
-        self.first[to].next_edge = self.edges.len() + 1;
-        self.edges.push(edge);
-        self.edges.push(edge2);
+    /// 添加一条从from到to的边，容量为weight，费用为默认值，返回边的编号
+    pub fn add_edge(&mut self, from : usize, to : usize, weight : &T) -> Result<EdgeId, FlowError> {
+        self.add_edge2(from, to, weight, &E::default())
     }
 
-    /// 添加一条从from到to的边，容量为weight，费用为cost
-    pub fn add_edge2(&mut self, from : usize, to : usize, weight : &T, cost : &E) {
+    /// 添加一条从from到to的边，容量为weight，费用为cost，返回边的编号
+    /// 
+    /// from或to不是合法的点时返回FlowError::InvalidNode
+    pub fn add_edge2(&mut self, from : usize, to : usize, weight : &T, cost : &E) -> Result<EdgeId, FlowError> {
+        self.check_node(from)?;
+        self.check_node(to)?;
+        Ok(self.push_edge(from, to, weight, cost))
+    }
+
+    // 添加一条边，不检查点的编号
+    fn push_edge(&mut self, from : usize, to : usize, weight : &T, cost : &E) -> EdgeId {
         let mut edge = Edge::create_edge(
             from, to, self.first[from].next_edge, 0, weight.clone(), cost.clone());
         let mut edge2 = Edge::create_edge(
             to, from, self.first[to].next_edge, 0, T::default(), cost.clone());
+        edge.capacity = weight.clone();
         edge.opp_edge = self.edges.len() + 1;
         edge2.opp_edge = self.edges.len();
         edge2.reversed = true;
@@ -228,8 +534,135 @@ impl<L, T, E, M> Graph<L, T, E, M>
         self.first[to].next_edge = self.edges.len() + 1;
         self.edges.push(edge);
         self.edges.push(edge2);
+        EdgeId(self.edges.len() - 2)
+    }
+
+    /// 添加一条从from到to的边，流量下界为lower，上界为upper，费用为cost，返回边的编号
+    /// 
+    /// lower大于upper时返回FlowError::Infeasible，附带边的两个端点。边的下界只在feasible_circulation、
+    /// max_flow_bounded和mcmf_bounded中被考虑，其他函数只将其看作容量为upper - lower的普通边。
+    pub fn add_edge_bounded(&mut self, from : usize, to : usize, lower : &T, upper : &T, cost : &E) -> Result<EdgeId, FlowError>
+        where T : Sub<Output = T> + PartialOrd {
+        self.check_node(from)?;
+        self.check_node(to)?;
+        if lower > upper {
+            return Err(FlowError::Infeasible(vec![from, to]));
+        }
+        let id = self.add_edge2(from, to, &(upper.clone() - lower.clone()), cost)?;
+        self.edges[id.0].lower = lower.clone();
+        Ok(id)
+    }
+
+    // 添加一条边并设置其上已有的流量，供读取文件时使用
+    pub(crate) fn add_edge_with_flow(&mut self, from : usize, to : usize, lower : &T, upper : &T, cost : &E, flow : &T)
+        -> Result<EdgeId, FlowError>
+        where T : Sub<Output = T> + PartialOrd {
+        self.check_node(from)?;
+        self.check_node(to)?;
+        if lower > upper {
+            return Err(FlowError::Infeasible(vec![from, to]));
+        }
+        if !(lower <= flow && flow <= upper) {
+            return Err(FlowError::MalformedFile(String::from("flow must be between the lower bound and the capacity")));
+        }
+        let id = self.add_edge_bounded(from, to, lower, upper, cost)?;
+        self.edges[id.0].weight = upper.clone() - flow.clone();
+        self.edges[id.0 + 1].weight = flow.clone() - lower.clone();
+        Ok(id)
+    }
+
+    /// 去掉所有边上的流量，将残量网络恢复为添加边时的状态
+    /// 
+    /// 之后可以对其他的源点和汇点重新求解，而不需要重新建图。边的下界仍然保留。
+    /// 
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// let mut g = Graph::<usize, u32>::create_graph(&[0, 1, 2]);
+    /// let a = g.add_edge(0, 1, &3).unwrap();
+    /// g.add_edge(1, 2, &2).unwrap();
+    /// assert_eq!(g.get_max_flow(0, 2), Ok(2));
+    /// g.reset_flow();
+    /// assert_eq!((g.get_flow(a), g.get_residual(a)), (Ok(0), Ok(3)));
+    /// assert_eq!(g.get_max_flow(0, 1), Ok(3));
+    /// ```
+    pub fn reset_flow(&mut self) {
+        for e in self.edges.iter_mut() {
+            e.weight = e.capacity.clone();
+        }
+    }
+
+    /// 边的起点和终点
+    /// 
+    /// 以下get_开头的边的查询函数在id不是图中正向边的编号时都返回FlowError::InvalidEdge
+    pub fn get_endpoints(&self, id : EdgeId) -> Result<(usize, usize), FlowError> {
+        self.check_edge(id)?;
+        Ok((self.edges[id.0].from, self.edges[id.0].to))
+    }
+
+    /// 边的费用
+    pub fn get_cost(&self, id : EdgeId) -> Result<E, FlowError> {
+        self.check_edge(id)?;
+        Ok(self.edges[id.0].cost.clone())
+    }
+
+    /// 边的流量下界，使用add_edge和add_edge2添加的边下界为0
+    pub fn get_lower(&self, id : EdgeId) -> Result<T, FlowError> {
+        self.check_edge(id)?;
+        Ok(self.edges[id.0].lower.clone())
+    }
+
+    /// 边在残量网络中的剩余容量
+    pub fn get_residual(&self, id : EdgeId) -> Result<T, FlowError> {
+        self.check_edge(id)?;
+        Ok(self.edges[id.0].weight.clone())
+    }
+}
+
+impl<L, T, E, M> Graph<L, T, E, M>
+    where
+        L : Hash,
+        E : Clone + Default,
+        T : Clone + Default + Add<Output = T>,
+        M : super::costtype::MulTE<T, E> {
+
+    // 正向边e上的实际流量，即下界加上残量网络中的流量
+    fn edge_flow(&self, e : usize) -> T {
+        self.edges[e].lower.clone() + self.edges[self.edges[e].opp_edge].weight.clone()
+    }
+
+    // 正向边e的容量上界
+    pub(crate) fn edge_capacity(&self, e : usize) -> T {
+        self.edges[e].lower.clone() + self.edges[e].capacity.clone()
+    }
+
+    /// 边的容量，即添加边时的容量上界
+    pub fn get_capacity(&self, id : EdgeId) -> Result<T, FlowError> {
+        self.check_edge(id)?;
+        Ok(self.edge_capacity(id.0))
     }
 
+    /// 边上当前的流量，包括下界的部分
+    /// 
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// let mut g = Graph::<usize, i32>::create_graph(&[0, 1, 2]);
+    /// let a = g.add_edge(0, 1, &3).unwrap();
+    /// let b = g.add_edge(1, 2, &2).unwrap();
+    /// g.get_max_flow(0, 2).unwrap();
+    /// assert_eq!((g.get_flow(a), g.get_residual(a), g.get_capacity(a)), (Ok(2), Ok(1), Ok(3)));
+    /// assert_eq!(g.get_flow(b), Ok(2));
+    /// ```
+    pub fn get_flow(&self, id : EdgeId) -> Result<T, FlowError> {
+        self.check_edge(id)?;
+        Ok(self.edge_flow(id.0))
+    }
+
+    /// 按照添加的顺序遍历所有添加的边(不包括反向边)，返回(边的编号, 起点, 终点, 流量)
+    pub fn edges_with_flow(&self) -> impl Iterator<Item = (EdgeId, usize, usize, T)> + '_ {
+        self.edges.iter().enumerate()
+            .filter(|(_, e)| !e.reversed)
+            .map(|(i, e)| (EdgeId(i), e.from, e.to, self.edge_flow(i)))
+    }
 }
 
 
@@ -240,19 +673,48 @@ impl<L, T, E, M> Graph<L, T, E, M>
         T : Clone + Default + Add<Output = T> + Sub<Output = T> + PartialEq + PartialOrd,
         M : super::costtype::MulTE<T, E> {
     /// 求从s到t的最大流
-    pub fn get_max_flow(&mut self, s : usize, t : usize) -> T {
-        self.dinic(s, t)
+    pub fn get_max_flow(&mut self, s : usize, t : usize) -> Result<T, FlowError> {
+        self.max_flow_with(s, t, MaxFlowAlgorithm::Dinic)
     }
 
-    fn bfs(&self, levels : &mut Vec<u32>, s : usize) {
+    /// 使用指定的算法求从s到t的最大流
+    /// 
+    /// 与get_max_flow相同，会在当前的残量网络上继续增广，返回新增的流量，
+    /// 之后仍然可以调用get_cut求最小割。
+    /// 
+    /// ```
+    /// use network_flow::graph::{Graph, MaxFlowAlgorithm};
+    /// let mut g = Graph::<usize, u32>::create_graph(&[0, 1, 2]);
+    /// g.add_edge(0, 1, &3).unwrap();
+    /// g.add_edge(1, 2, &2).unwrap();
+    /// assert_eq!(g.max_flow_with(0, 2, MaxFlowAlgorithm::PushRelabel), Ok(2));
+    /// assert_eq!(g.get_cut(0), Ok(vec![0, 1]));
+    /// ```
+    pub fn max_flow_with(&mut self, s : usize, t : usize, algorithm : MaxFlowAlgorithm) -> Result<T, FlowError> {
+        self.check_node(s)?;
+        self.check_node(t)?;
+        Ok(match algorithm {
+            MaxFlowAlgorithm::Dinic => self.dinic(s, t),
+            MaxFlowAlgorithm::PushRelabel => self.push_relabel(s, t)
+        })
+    }
+
+    // 沿编号为index的边推送f的流量，同时更新其反向边
+    fn push_flow(&mut self, index : usize, f : T) {
+        self.edges[index].weight = self.edges[index].weight.clone() - f.clone();
+        let opp = self.edges[index].opp_edge;
+        self.edges[opp].weight = self.edges[opp].weight.clone() + f;
+    }
+
+    fn bfs(&self, levels : &mut [u32], s : usize) {
         levels[s] = 1;
         let mut q1 = vec![];
         let mut q2 = vec![];
         q2.push(s);
         while ! q1.is_empty() || ! q2.is_empty() {
             if q1.is_empty() {
-                while !q2.is_empty() {
-                    q1.push(q2.pop().unwrap());
+                while let Some(x) = q2.pop() {
+                    q1.push(x);
                 }
             }
             let now = q1.pop().unwrap();
@@ -268,55 +730,76 @@ impl<L, T, E, M> Graph<L, T, E, M>
         }
     }
 
-    fn dfs(&mut self, now : usize, t : usize, levels : &Vec<u32>, flow : T) -> T {
-        if now == t {
-            flow
-        }
-        else {
-            let edges = self.get_all_edges(now);
-            let mut v = vec![];
-            for (edge, index) in edges {
-                v.push((edge.weight.clone(), edge.to, index));
-            }
-            let mut res = T::default();
-            for (w, x, index) in v {
-                if w != T::default() && levels[x] == levels[now] + 1 {
-                    if flow == T::default() {
-                        res = self.dfs(x, t, levels, w);
-                    }
-                    else if flow < w {
-                        res = self.dfs(x, t, levels, flow.clone());
-                    }
-                    else {
-                        res = self.dfs(x, t, levels, w);
+    // 在bfs得到的分层图上求阻塞流
+    //
+    // 使用显式的栈保存当前的路径，并对每个点记录当前弧，
+    // 每找到一条增广路后只回退到第一条满流的边，继续寻找下一条增广路
+    fn blocking_flow(&mut self, s : usize, t : usize, levels : &mut [u32]) -> T {
+        let mut current : Vec<usize> = self.first.iter().map(|x| x.next_edge).collect();
+        let mut path : Vec<usize> = vec![];
+        let mut res = T::default();
+        let mut now = s;
+        loop {
+            if now == t {
+                let mut f = self.edges[path[0]].weight.clone();
+                for &e in &path[1..] {
+                    if self.edges[e].weight < f {
+                        f = self.edges[e].weight.clone();
                     }
-                    if res != T::default() {
-                        self.edges[index].weight = self.edges[index].weight.clone() - res.clone();
-                        let temp = self.edges[index].opp_edge;
-                        self.edges[temp].weight = self.edges[temp].weight.clone() + res.clone();
-                        return res;
+                }
+                let mut back = path.len();
+                for (i, &e) in path.iter().enumerate() {
+                    self.push_flow(e, f.clone());
+                    if back == path.len() && self.edges[e].weight == T::default() {
+                        back = i;
                     }
                 }
+                res = res + f;
+                path.truncate(back);
+                now = match path.last() {
+                    Some(&e) => self.edges[e].to,
+                    None => s
+                };
+                continue;
+            }
+            let mut e = current[now];
+            while e != usize::MAX {
+                let x = self.edges[e].to;
+                if self.edges[e].weight != T::default() && levels[x] == levels[now] + 1 {
+                    break;
+                }
+                e = self.edges[e].next_edge;
+            }
+            current[now] = e;
+            if e != usize::MAX {
+                path.push(e);
+                now = self.edges[e].to;
+            }
+            else {
+                if now == s {
+                    break res;
+                }
+                // 从now无法再到达t，将其移出分层图
+                levels[now] = 0;
+                let e = path.pop().unwrap();
+                now = self.edges[e].from;
+                current[now] = self.edges[e].next_edge;
             }
-            res
         }
     }
 
     fn dinic(&mut self, s : usize, t : usize) -> T {
         let mut res = T::default();
+        if s == t {
+            return res;
+        }
         loop {
             let mut levels = vec![0; self.labels.len()];
             self.bfs(&mut levels, s);
             if levels[t] == 0 {
                 break res
             }
-            loop {
-                let temp = self.dfs(s, t, &levels, T::default());
-                if temp == T::default() {
-                    break
-                }
-                res = res + temp;
-            }
+            res = res + self.blocking_flow(s, t, &mut levels);
         }
     }
 
@@ -330,15 +813,21 @@ impl<L, T, E, M> Graph<L, T, E, M>
     /// use network_flow::graph::Graph;
     /// let mut g = Graph::<usize, i32>::new();
     /// // 建图
-    /// g.get_max_flow(0, 10);
-    /// let v = g.get_cut(0);
+    /// g.get_max_flow(0, 10).unwrap();
+    /// let v = g.get_cut(0).unwrap();
     /// ```
-    pub fn get_cut(&self, s : usize) -> Vec<usize> {
+    pub fn get_cut(&self, s : usize) -> Result<Vec<usize>, FlowError> {
+        self.check_node(s)?;
+        Ok(self.reachable(s))
+    }
+
+    // 残量网络中从s出发能到达的点
+    fn reachable(&self, s : usize) -> Vec<usize> {
         let mut levels = vec![0; self.labels.len()];
         self.bfs(&mut levels, s);
         let mut res = vec![];
-        for i in 0..self.labels.len() {
-            if levels[i] != 0 {
+        for (i, level) in levels.iter().enumerate() {
+            if *level != 0 {
                 res.push(i);
             }
         }
@@ -353,21 +842,66 @@ impl<L, T, E, M> Graph<L, T, E, M>
         T : Clone + Default + Add<Output = T> + Sub<Output = T> + PartialEq + PartialOrd,
         M : super::costtype::MulTE<T, E> {
 
-    fn spfa(&self, s : usize, t : usize, dist : &mut [E]) -> bool {
+    // 编号为index的边在残量网络中的费用，反向边的费用为原费用的相反数
+    fn arc_cost(&self, index : usize) -> E {
+        if self.edges[index].reversed {
+            E::default() - self.edges[index].cost.clone()
+        }
+        else {
+            self.edges[index].cost.clone()
+        }
+    }
+
+    // 以h为初值，在残量网络上从所有点同时开始做spfa，
+    // 得到的势使所有残量边的约化费用c(u, v) + h(u) - h(v)都非负
+    // 
+    // 要求残量网络中没有负环
+    fn residual_potentials(&self, mut h : Vec<E>) -> Vec<E> {
+        let n = self.labels.len();
+        let mut q : VecDeque<usize> = (0..n).collect();
+        let mut inque = vec![true; n];
+        while let Some(now) = q.pop_front() {
+            inque[now] = false;
+            let mut e = self.first[now].next_edge;
+            while e != usize::MAX {
+                let x = self.edges[e].to;
+                if self.edges[e].weight != T::default() {
+                    let d = h[now].clone() + self.arc_cost(e);
+                    if d < h[x] {
+                        h[x] = d;
+                        if !inque[x] {
+                            inque[x] = true;
+                            q.push_back(x);
+                        }
+                    }
+                }
+                e = self.edges[e].next_edge;
+            }
+        }
+        h
+    }
+
+    // 从t出发反向求每个点到t的最短路，返回s是否能到达t
+    //
+    // 如果遇到了负环，返回环上的点
+    fn spfa(&self, s : usize, t : usize, dist : &mut [E]) -> Result<bool, FlowError> {
+        let n = self.labels.len();
         let mut q = VecDeque::new();
         q.push_back(t); dist[t] = E::default();
-        let mut vis = vec![false; self.labels.len()];
-        let mut inque = vec![false; self.labels.len()];
+        let mut vis = vec![false; n];
+        let mut inque = vec![false; n];
+        let mut pre = vec![usize::MAX; n];
+        let mut cnt = vec![0usize; n];
         inque[t] = true;
         vis[t] = true;
         while !q.is_empty() {
             let now = q.pop_front().unwrap();
-            let edges = self.get_all_edges(now);
+            let edges = self.all_edges(now);
             let mut v = vec![];
             for (edge, _) in edges {
-                v.push((self.edges[edge.opp_edge].weight.clone(), edge.cost.clone(), edge.to, edge.reversed));
+                v.push((self.edges[edge.opp_edge].weight.clone(), edge.cost.clone(), edge.to, edge.reversed, edge.opp_edge));
             }
-            for (w, c, to, r) in v {
+            for (w, c, to, r, opp) in v {
                 if w == T::default() {
                     continue;
                 }
@@ -375,6 +909,13 @@ impl<L, T, E, M> Graph<L, T, E, M>
                 if !vis[to] || dist[to] > newcost {
                     vis[to] = true;
                     dist[to] = newcost;
+                    pre[to] = opp;
+                    cnt[to] = cnt[now] + 1;
+                    if cnt[to].is_multiple_of(n) {
+                        if let Some(cycle) = self.find_pre_cycle(&pre, false) {
+                            return Err(FlowError::NegativeCycle(cycle.into_iter().map(|e| self.edges[e].from).collect()));
+                        }
+                    }
                     if !inque[to] {
                         inque[to] = true;
                         if q.is_empty() || dist[*q.front().unwrap()] < dist[to] {
@@ -388,7 +929,7 @@ impl<L, T, E, M> Graph<L, T, E, M>
             }
             inque[now] = false;
         }
-        vis[s]
+        Ok(vis[s])
     }
 
     fn mcmf_dfs(&mut self, now : usize, flow : T, cost : &mut E, dist : &[E], vis : &mut [bool], t : usize) -> T {
@@ -397,7 +938,7 @@ impl<L, T, E, M> Graph<L, T, E, M>
             flow
         }
         else {
-            let edges = self.get_all_edges(now);
+            let edges = self.all_edges(now);
             let mut v = vec![];
             for (edge, index) in edges {
                 v.push((edge.opp_edge, edge.weight.clone(), edge.cost.clone(), edge.to, edge.reversed, index));
@@ -418,7 +959,7 @@ impl<L, T, E, M> Graph<L, T, E, M>
                     f = self.mcmf_dfs(to, w, cost, dist, vis, t);
                 }
                 if f != T::default() {
-                    *cost = cost.clone() + M::mul(&f, &c);
+                    *cost = if r { cost.clone() - M::mul(&f, &c) } else { cost.clone() + M::mul(&f, &c) };
                     self.edges[i].weight = self.edges[i].weight.clone() - f.clone();
                     self.edges[opp].weight = self.edges[opp].weight.clone() + f.clone();
                     return f;
@@ -428,265 +969,181 @@ impl<L, T, E, M> Graph<L, T, E, M>
         }
     }
 
-    /// 求从s到t的最小费用最大流
-    pub fn mcmf(&mut self, s : usize, t : usize) -> (T, E) {
+    // 连续最短路算法，limit为流量的上限，None表示不限制
+    //
+    // only_negative为true时，在最短路的费用变为非负时停止增广，此时得到的是费用最小的流。
+    // 如果残量网络中有负环，返回环上的点
+    fn ssp(&mut self, s : usize, t : usize, limit : Option<T>, only_negative : bool) -> Result<(T, E), FlowError> {
+        self.check_node(s)?;
+        self.check_node(t)?;
         let mut cost = E::default();
         let mut flow = T::default();
+        if s == t || limit == Some(T::default()) {
+            return Ok((flow, cost));
+        }
         let mut dist = vec![E::default(); self.labels.len()];
-        while self.spfa(s, t,&mut dist) {
+        while self.spfa(s, t,&mut dist)? {
+            if only_negative && dist[s] >= E::default() {
+                break;
+            }
             let mut vis = vec![false; self.labels.len()];
             vis[t] = true;
             while vis[t] {
-                for i in &mut vis {
-                    *i = false;
+                vis.fill(false);
+                let rest = match &limit {
+                    Some(k) => k.clone() - flow.clone(),
+                    None => T::default()
+                };
+                flow = flow + self.mcmf_dfs(s, rest, &mut cost, &dist, &mut vis, t);
+                if limit.as_ref() == Some(&flow) {
+                    return Ok((flow, cost));
                 }
-                flow = flow + self.mcmf_dfs(s, T::default(), &mut cost, &dist, &mut vis, t);
             }
         }
-        (flow, cost)
+        Ok((flow, cost))
+    }
+
+    /// 求从s到t的最小费用最大流
+    /// 
+    /// 返回(流量, 费用)。如果残量网络中出现了负环，返回FlowError::NegativeCycle以及环上的点，
+    /// 此时可以使用mcmf_cycle_canceling求解。
+    /// 
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// # use network_flow::error::FlowError;
+    /// let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2]);
+    /// g.add_edge2(0, 1, &2, &1).unwrap();
+    /// g.add_edge2(1, 2, &2, &-3).unwrap();
+    /// g.add_edge2(2, 1, &2, &1).unwrap();
+    /// assert_eq!(g.mcmf(0, 2), Err(FlowError::NegativeCycle(vec![1, 2])));
+    /// ```
+    pub fn mcmf(&mut self, s : usize, t : usize) -> Result<(T, E), FlowError> {
+        self.ssp(s, t, None, false)
     }
-}
 
-use crate::io::BitIO;
+    /// 求从s到t流量为k的最小费用流
+    /// 
+    /// 如果最大流小于k，则得到的是最小费用最大流。返回实际的(流量, 费用)，
+    /// 结束后残量网络中为一个合法的流，可以继续调用其他函数增广。遇到负环时与mcmf相同。
+    /// 
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2]);
+    /// g.add_edge2(0, 1, &2, &1).unwrap();
+    /// g.add_edge2(1, 2, &1, &3).unwrap();
+    /// g.add_edge2(0, 2, &2, &5).unwrap();
+    /// assert_eq!(g.min_cost_flow_k(0, 2, &2), Ok((2, 9)));
+    /// assert_eq!(g.min_cost_flow_k(0, 2, &5), Ok((1, 5)));
+    /// ```
+    pub fn min_cost_flow_k(&mut self, s : usize, t : usize, k : &T) -> Result<(T, E), FlowError> {
+        self.ssp(s, t, Some(k.clone()), false)
+    }
 
-impl<L, T, E, M : super::costtype::MulTE<T, E>> Graph<L, T, E, M> 
-    where
-        L : BitIO + Clone + Hash + Eq,
-        E : BitIO + Clone + Default,
-        T : BitIO + Clone + Default {
-    /// 将当前的图的状态输出到文件中
+    /// 求从s到t费用最小的流，流量不要求最大
     /// 
-    /// L, T, E均需实现BitIO trait
-    pub fn output_file(&self, file : &str) -> Result<(), Error> {
-        let mut fs = File::create(file)?;
-        fs.write(&self.labels.len().to_be_bytes())?;
-        for i in &self.labels {
-            let temp = i.to_bit();
-            fs.write(&temp.len().to_be_bytes())?;
-            fs.write(&temp)?;
-        }
-        fs.write(&self.edges.len().to_be_bytes())?;
-        for edge in &self.edges {
-            fs.write(&edge.from.to_be_bytes())?;
-            fs.write(&edge.to.to_be_bytes())?;
-            fs.write(&edge.next_edge.to_be_bytes())?;
-            fs.write(&edge.opp_edge.to_be_bytes())?;
-            fs.write(&(edge.reversed as u8).to_be_bytes())?;
-            let temp = edge.weight.to_bit();
-            fs.write(&temp.len().to_be_bytes())?;
-            fs.write(&temp)?;
-            let temp = edge.cost.to_bit();
-            fs.write(&temp.len().to_be_bytes())?;
-            fs.write(&temp)?;
-        }
-        fs.write(&self.first.len().to_be_bytes())?;
-        for edge in &self.first {
-            fs.write(&edge.from.to_be_bytes())?;
-            fs.write(&edge.to.to_be_bytes())?;
-            fs.write(&edge.next_edge.to_be_bytes())?;
-            fs.write(&edge.opp_edge.to_be_bytes())?;
-            fs.write(&(edge.reversed as u8).to_be_bytes())?;
-            let temp = edge.weight.to_bit();
-            fs.write(&temp.len().to_be_bytes())?;
-            fs.write(&temp)?;
-            let temp = edge.cost.to_bit();
-            fs.write(&temp.len().to_be_bytes())?;
-            fs.write(&temp)?;
-        }
-        Ok(())
+    /// 当最短增广路的费用变为非负时停止增广，返回(流量, 费用)，其中费用是所有流量中最小的。
+    /// 适用于费用为负数、需要最大化收益的情形，遇到负环时与mcmf相同。
+    /// 
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2]);
+    /// g.add_edge2(0, 1, &3, &-4).unwrap();
+    /// g.add_edge2(1, 2, &2, &1).unwrap();
+    /// g.add_edge2(0, 2, &5, &2).unwrap();
+    /// g.add_edge2(1, 2, &3, &6).unwrap();
+    /// assert_eq!(g.min_cost_flow_any(0, 2), Ok((2, -6)));
+    /// ```
+    pub fn min_cost_flow_any(&mut self, s : usize, t : usize) -> Result<(T, E), FlowError> {
+        self.ssp(s, t, None, true)
     }
-    /// 从文件中生成一个图
+
+    /// 使用指定的算法求从s到t的最小费用最大流
+    /// 
+    /// 返回值与mcmf相同，为(流量, 费用)，遇到负环时返回FlowError::NegativeCycle
     /// 
-    /// L, T, E均需实现BitIO trait
-    pub fn input_file(file : &str) -> Result<Self, Error> {
-        let mut res = Self::new();
-        let mut fs = File::open(file)?;
-        let mut buf = [0; size_of::<usize>()];
-        assert_eq!(fs.read(&mut buf)?, size_of::<usize>());
-        let len = usize::from_be_bytes(buf);
-        for _ in 0..len {
-            assert_eq!(fs.read(&mut buf)?, size_of::<usize>());
-            let len = usize::from_be_bytes(buf);
-            let mut buf2 = vec![0; len];
-            assert_eq!(fs.read(&mut buf2)?, len);
-            let l = L::from_bit(&buf2);
-            res.labels.push(l);
-        }
-        assert_eq!(fs.read(&mut buf)?, size_of::<usize>());
-        let len = usize::from_be_bytes(buf);
-        for _ in 0..len {
-            assert_eq!(fs.read(&mut buf)?, size_of::<usize>());
-            let from = usize::from_be_bytes(buf);
-
-            assert_eq!(fs.read(&mut buf)?, size_of::<usize>());
-            let to = usize::from_be_bytes(buf);
-
-            assert_eq!(fs.read(&mut buf)?, size_of::<usize>());
-            let next_edge = usize::from_be_bytes(buf);
-
-            assert_eq!(fs.read(&mut buf)?, size_of::<usize>());
-            let opp_edge = usize::from_be_bytes(buf);
-
-            let mut buf3 = [0];
-            assert_eq!(fs.read(&mut buf3)?, 1);
-            let reversed = u8::from_be_bytes(buf3) != 0;
-
-            assert_eq!(fs.read(&mut buf)?, size_of::<usize>());
-            let len = usize::from_be_bytes(buf);
-            let mut buf2 = vec![0; len];
-            assert_eq!(fs.read(&mut buf2)?, len);
-            let weight = T::from_bit(&buf2);
-
-            assert_eq!(fs.read(&mut buf)?, size_of::<usize>());
-            let len = usize::from_be_bytes(buf);
-            let mut buf2 = vec![0; len];
-            assert_eq!(fs.read(&mut buf2)?, len);
-            let cost = E::from_bit(&buf2);
-            
-            res.edges.push(Edge{from, to, next_edge, opp_edge, reversed, weight, cost});
-        }
-        assert_eq!(fs.read(&mut buf)?, size_of::<usize>());
-        let len = usize::from_be_bytes(buf);
-        for _ in 0..len {
-            assert_eq!(fs.read(&mut buf)?, size_of::<usize>());
-            let from = usize::from_be_bytes(buf);
-
-            assert_eq!(fs.read(&mut buf)?, size_of::<usize>());
-            let to = usize::from_be_bytes(buf);
-
-            assert_eq!(fs.read(&mut buf)?, size_of::<usize>());
-            let next_edge = usize::from_be_bytes(buf);
-
-            assert_eq!(fs.read(&mut buf)?, size_of::<usize>());
-            let opp_edge = usize::from_be_bytes(buf);
-
-            let mut buf3 = [0];
-            assert_eq!(fs.read(&mut buf3)?, 1);
-            let reversed = u8::from_be_bytes(buf3) != 0;
-
-            assert_eq!(fs.read(&mut buf)?, size_of::<usize>());
-            let len = usize::from_be_bytes(buf);
-            let mut buf2 = vec![0; len];
-            assert_eq!(fs.read(&mut buf2)?, len);
-            let weight = T::from_bit(&buf2);
-
-            assert_eq!(fs.read(&mut buf)?, size_of::<usize>());
-            let len = usize::from_be_bytes(buf);
-            let mut buf2 = vec![0; len];
-            assert_eq!(fs.read(&mut buf2)?, len);
-            let cost = E::from_bit(&buf2);
-            
-            res.first.push(Edge{from, to, next_edge, opp_edge, reversed, weight, cost});
+    /// ```
+    /// use network_flow::graph::{Graph, McmfAlgorithm};
+    /// let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2]);
+    /// g.add_edge2(0, 1, &2, &1).unwrap();
+    /// g.add_edge2(1, 2, &1, &3).unwrap();
+    /// g.add_edge2(0, 2, &1, &5).unwrap();
+    /// assert_eq!(g.mcmf_with(0, 2, McmfAlgorithm::PrimalDual), Ok((2, 9)));
+    /// assert_eq!(g.mcmf_with(0, 2, McmfAlgorithm::CycleCanceling), Ok((0, 0)));
+    /// ```
+    pub fn mcmf_with(&mut self, s : usize, t : usize, algorithm : McmfAlgorithm) -> Result<(T, E), FlowError> {
+        match algorithm {
+            McmfAlgorithm::Spfa => self.mcmf(s, t),
+            McmfAlgorithm::PrimalDual => self.primal_dual(s, t),
+            McmfAlgorithm::CycleCanceling => self.mcmf_cycle_canceling(s, t)
         }
-        Ok(res)
     }
 }
 
-use crate::io::StrIO;
-use graphviz_rust_bla::parse;
-use graphviz_rust_bla::printer::{PrinterContext, DotPrinter};
-
-impl <L, T, E, M : super::costtype::MulTE<T, E>> Graph<L, T, E, M> 
-where
-    L : StrIO + Clone + Hash + Eq,
-    E : StrIO + Clone + Default + Add<Output = E> + Sub<Output = E>,
-    T : StrIO + Clone + Default + Add<Output = T> + Sub<Output = T>, {
-    /// 将图输出到.dot文件中
-    pub fn output_to_dot(&self, file : &str) -> Result<(), Error> {
-        use dot_structures::*;
-        use dot_generator::*;
-        let mut fs = File::create(file)?;
-        let mut g = graph!(di id!("test"));
-        let mut temp = 0;
-        for l in &self.labels {
-            g.add_stmt(stmt!(node!(temp.to_str();attr!("label",l.to_str()))));
-            temp = temp + 1;
-        }
-        for e in &self.edges {
-            if e.reversed { continue; }
-            let temp = self.edges[e.opp_edge].weight.clone();
-            let tot = temp.clone() + e.weight.clone();
-            let mut s = String::from("\"");
-            s.push_str(&temp.to_str());
-            s.push('/');
-            s.push_str(&tot.to_str());
-            s.push(',');
-            s.push_str(&e.cost.to_str());
-            s.push('"');
-            g.add_stmt(stmt!(edge!(node_id!(e.from) => node_id!(e.to);attr!("label",s))));
-        }
-        let mut ctx = PrinterContext::default();
-        fs.write_all(g.print(&mut ctx).as_bytes())?;
-        Ok(())
+impl<L, T, E, M> Graph<L, T, E, M> 
+    where 
+        L : Clone + Hash + Eq,
+        E : Clone + Default,
+        T : Clone + Default + Add<Output = T> + Sub<Output = T> + PartialEq + PartialOrd,
+        M : super::costtype::MulTE<T, E> {
+
+    // 查找标签对应的编号，不存在时返回FlowError::UnknownLabel
+    fn index_or_err(&self, label : &L) -> Result<usize, FlowError> {
+        self.get_index(label).ok_or(FlowError::UnknownLabel)
     }
 
-    fn get_from_id(x : &dot_structures::Id) -> &String {
-        match x {
-            dot_structures::Id::Html(s) => s,
-            dot_structures::Id::Escaped(s) => s,
-            dot_structures::Id::Plain(s) => s,
-            dot_structures::Id::Anonymous(s) => s,
-        }
-    }
-
-    fn get_from_vertex(x : &dot_structures::Vertex) -> usize {
-        match x {
-            dot_structures::Vertex::N(x) => usize::from_str(Self::get_from_id(&x.0)),
-            _ => 0,
-        }
-    }
-
-    /// 从.dot文件中读取图
-    pub fn from_dot(file : &str) -> Result<Self, Error> {
-        let mut res = Self::new();
-        use dot_structures::Graph::DiGraph;
-        use dot_structures::Stmt::*;
-        let mut fs = File::open(file)?;
-        let mut buf = vec![];
-        fs.read_to_end(&mut buf)?;
-        let s = parse(&String::from_utf8(buf).unwrap()).unwrap();
-        match s {
-            DiGraph { id : _, strict : _, stmts } => {
-                for stmt in stmts {
-                    match stmt {
-                        Node(node) => {
-                            res.add_node(&L::from_str(Self::get_from_id(&node.attributes[0].1)));
-                        },
-                        Edge(edge) => {
-                            let mut from = 0;
-                            let mut to = 0;
-                            match edge.ty {
-                                dot_structures::EdgeTy::Pair(u, v) => {
-                                    from = Self::get_from_vertex(&u);
-                                    to = Self::get_from_vertex(&v);
-                                },
-                                _ => {}
-                            }
-                            if from == to {
-                                panic!("from_dot : invalid edge form");
-                            }
-                            let mut s = Self::get_from_id(&edge.attributes[0].1).clone().split_off(1);
-                            let p = s.find('/').unwrap();
-                            let mut ss = s.split_off(p);
-                            let w = T::from_str(&s);
-                            s = ss.split_off(1);
-                            let p = s.find(',').unwrap();
-                            ss = s.split_off(p);
-                            let ww = T::from_str(&s);
-                            s = ss.split_off(1);
-                            s.truncate(s.len() - 1);
-                            let c = E::from_str(&s);
-                            res.add_edge2(from, to, &ww, &c);
-                            let l = res.edges.len();
-                            res.edges[l - 1].weight = res.edges[l - 1].weight.clone() + w.clone();
-                            res.edges[l - 2].weight = res.edges[l - 2].weight.clone() - w.clone();
-                        },
-                        _ => ()
-                    }
-                }
-            },
-            _ => {panic!("from_dot : invalid graph form");}
-        }
-        Ok(res)
+    /// 在标签为from和to的点之间添加一条容量为weight的边，不存在的点会被先添加
+    pub fn add_edge_by_label(&mut self, from : &L, to : &L, weight : &T) -> EdgeId {
+        let from = self.get_or_add_node(from);
+        let to = self.get_or_add_node(to);
+        self.push_edge(from, to, weight, &E::default())
+    }
+
+    /// 在标签为from和to的点之间添加一条容量为weight、费用为cost的边，不存在的点会被先添加
+    pub fn add_edge2_by_label(&mut self, from : &L, to : &L, weight : &T, cost : &E) -> EdgeId {
+        let from = self.get_or_add_node(from);
+        let to = self.get_or_add_node(to);
+        self.push_edge(from, to, weight, cost)
     }
-}
\ No newline at end of file
+
+    /// 求从标签为s的点到标签为t的点的最大流，点不存在时返回FlowError::UnknownLabel
+    /// 
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// use network_flow::error::FlowError;
+    /// let mut g = Graph::<&str, i32>::new();
+    /// g.add_edge_by_label(&"s", &"a", &3);
+    /// g.add_edge_by_label(&"a", &"t", &2);
+    /// assert_eq!(g.get_max_flow_by_label(&"s", &"t"), Ok(2));
+    /// assert_eq!(g.get_cut_by_label(&"s"), Ok(vec!["s", "a"]));
+    /// assert_eq!(g.get_max_flow_by_label(&"s", &"b"), Err(FlowError::UnknownLabel));
+    /// ```
+    pub fn get_max_flow_by_label(&mut self, s : &L, t : &L) -> Result<T, FlowError> {
+        let s = self.index_or_err(s)?;
+        let t = self.index_or_err(t)?;
+        self.get_max_flow(s, t)
+    }
+
+    /// 求最大流之后，返回最小割中与标签为s的点在同一侧的点的标签
+    pub fn get_cut_by_label(&self, s : &L) -> Result<Vec<L>, FlowError> {
+        let s = self.index_or_err(s)?;
+        Ok(self.reachable(s).into_iter().map(|x| self.labels[x].clone()).collect())
+    }
+
+    /// 求从标签为s的点到标签为t的点的最小费用最大流
+    /// 
+    /// 点不存在时返回FlowError::UnknownLabel，遇到负环时与mcmf相同。
+    /// 
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// let mut g = Graph::<String, i32, i32>::new();
+    /// g.add_edge2_by_label(&"s".to_string(), &"a".to_string(), &2, &1);
+    /// g.add_edge2_by_label(&"a".to_string(), &"t".to_string(), &3, &2);
+    /// assert_eq!(g.mcmf_by_label(&"s".to_string(), &"t".to_string()), Ok((2, 6)));
+    /// ```
+    pub fn mcmf_by_label(&mut self, s : &L, t : &L) -> Result<(T, E), FlowError>
+        where E : Add<Output = E> + Sub<Output = E> + PartialEq + PartialOrd {
+        let s = self.index_or_err(s)?;
+        let t = self.index_or_err(t)?;
+        self.mcmf(s, t)
+    }
+}
@@ -0,0 +1,351 @@
+//! 网络单纯形法求带有点供需的最小费用流
+//!
+//! 使用一个人工根节点和大M法构造初始的强可行生成树，
+//! 入基边使用分块搜索(block search)选取，出基边按照强可行树的规则选取以避免循环。
+
+use super::Graph;
+use crate::error::FlowError;
+use core::ops::Add;
+use core::ops::Sub;
+use std::hash::Hash;
+
+// 边在生成树中的状态
+const STATE_UPPER : i8 = -1;
+const STATE_TREE : i8 = 0;
+const STATE_LOWER : i8 = 1;
+
+// 带有大M的费用，big为大M的系数，按照字典序比较
+#[derive(Clone)]
+struct BigCost<E> {
+    big : i64,
+    small : E
+}
+
+impl<E> BigCost<E>
+    where
+        E : Clone + Default + Add<Output = E> + Sub<Output = E> + PartialOrd {
+    fn add(&self, other : &Self) -> Self {
+        BigCost { big : self.big + other.big, small : self.small.clone() + other.small.clone() }
+    }
+
+    fn sub(&self, other : &Self) -> Self {
+        BigCost { big : self.big - other.big, small : self.small.clone() - other.small.clone() }
+    }
+
+    fn less(&self, other : &Self) -> bool {
+        self.big < other.big || (self.big == other.big && self.small < other.small)
+    }
+
+    fn zero() -> Self {
+        BigCost { big : 0, small : E::default() }
+    }
+}
+
+// 比较两个剩余容量，None表示无穷大
+fn less_cap<T : PartialOrd>(a : &Option<T>, b : &Option<T>) -> bool {
+    match (a, b) {
+        (Some(x), Some(y)) => x < y,
+        (Some(_), None) => true,
+        _ => false
+    }
+}
+
+impl<L, T, E, M> Graph<L, T, E, M>
+    where
+        L : Clone + Hash + Eq,
+        E : Clone + Default + Add<Output = E> + Sub<Output = E> + PartialEq + PartialOrd,
+        T : Clone + Default + Add<Output = T> + Sub<Output = T> + PartialEq + PartialOrd,
+        M : crate::costtype::MulTE<T, E> {
+
+    /// 使用网络单纯形法求最小费用流
+    ///
+    /// supply\[i\]和demand\[i\]分别为点i的供给量和需求量（缺省为0），
+    /// 要求每个点流出的流量减去流入的流量恰好等于supply\[i\] - demand\[i\]。
+    ///
+    /// 计算时会忽略图中原有的流量，结束后每条边上的流量保存在残量网络中。
+    ///
+    /// 如果有可行解，返回(总费用, 每个点的势)，势h满足对残量网络中的每条边都有c(u, v) + h(u) - h(v) >= 0；
+    /// 否则返回FlowError::Infeasible以及供需无法被满足的点，残量网络保持不变。
+    ///
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2]);
+    /// g.add_edge2(0, 1, &5, &2).unwrap();
+    /// g.add_edge2(1, 2, &5, &1).unwrap();
+    /// g.add_edge2(0, 2, &2, &4).unwrap();
+    /// let (cost, _) = g.network_simplex(&[4, 0, 0], &[0, 0, 4]).unwrap();
+    /// assert_eq!(cost, 12);
+    /// ```
+    pub fn network_simplex(&mut self, supply : &[T], demand : &[T]) -> Result<(E, Vec<E>), FlowError> {
+        let n = self.labels.len();
+        let root = n;
+        let mut src = vec![];
+        let mut tgt = vec![];
+        let mut cap = vec![];
+        let mut cost = vec![];
+        let mut flow = vec![];
+        let mut index = vec![];
+        for (i, e) in self.edges.iter().enumerate() {
+            if e.reversed {
+                continue;
+            }
+            src.push(e.from);
+            tgt.push(e.to);
+            cap.push(Some(e.weight.clone() + self.edges[e.opp_edge].weight.clone()));
+            cost.push(BigCost { big : 0, small : e.cost.clone() });
+            flow.push(T::default());
+            index.push(i);
+        }
+        let m = src.len();
+
+        // 人工边和初始的生成树
+        let mut state = vec![STATE_LOWER; m];
+        let mut parent = vec![root; n + 1];
+        let mut pred = vec![usize::MAX; n + 1];
+        let mut depth = vec![1; n + 1];
+        let mut pi = vec![BigCost::<E>::zero(); n + 1];
+        let mut adj = vec![vec![]; n + 1];
+        let mut pos = vec![[0, 0]; m];
+        parent[root] = usize::MAX;
+        depth[root] = 0;
+        for v in 0..n {
+            let s = supply.get(v).cloned().unwrap_or_default();
+            let d = demand.get(v).cloned().unwrap_or_default();
+            let a = src.len();
+            if s >= d {
+                src.push(v);
+                tgt.push(root);
+                flow.push(s - d);
+                pi[v] = BigCost { big : -1, small : E::default() };
+            }
+            else {
+                src.push(root);
+                tgt.push(v);
+                flow.push(d - s);
+                pi[v] = BigCost { big : 1, small : E::default() };
+            }
+            cap.push(None);
+            cost.push(BigCost { big : 1, small : E::default() });
+            state.push(STATE_TREE);
+            pred[v] = a;
+            pos.push([adj[src[a]].len(), adj[tgt[a]].len()]);
+            adj[src[a]].push(a);
+            adj[tgt[a]].push(a);
+        }
+        let total = src.len();
+
+        let block = ((total as f64).sqrt() as usize).max(10);
+        let mut next_arc = 0;
+        loop {
+            // 分块搜索入基边
+            let mut enter = usize::MAX;
+            let mut best = BigCost::<E>::zero();
+            let mut cnt = 0;
+            for k in 0..total {
+                let a = (next_arc + k) % total;
+                cnt += 1;
+                if state[a] != STATE_TREE && cap[a] != Some(T::default()) {
+                    let rc = cost[a].add(&pi[src[a]]).sub(&pi[tgt[a]]);
+                    let violation = if state[a] == STATE_LOWER { BigCost::zero().sub(&rc) } else { rc };
+                    if best.less(&violation) {
+                        best = violation;
+                        enter = a;
+                    }
+                }
+                if cnt == block {
+                    if enter != usize::MAX {
+                        next_arc = (a + 1) % total;
+                        break;
+                    }
+                    cnt = 0;
+                }
+            }
+            if enter == usize::MAX {
+                break;
+            }
+
+            // 找到入基边与生成树形成的环
+            let a = enter;
+            let (first, second) = if state[a] == STATE_LOWER { (src[a], tgt[a]) } else { (tgt[a], src[a]) };
+            let (mut x, mut y) = (first, second);
+            while x != y {
+                if depth[x] > depth[y] {
+                    x = parent[x];
+                }
+                else if depth[y] > depth[x] {
+                    y = parent[y];
+                }
+                else {
+                    x = parent[x];
+                    y = parent[y];
+                }
+            }
+            let apex = x;
+
+            // 沿环的方向求最小的剩余容量，并按强可行树的规则选出基边
+            let residual = |e : usize, forward : bool, flow : &Vec<T>| -> Option<T> {
+                if forward {
+                    cap[e].clone().map(|c| c - flow[e].clone())
+                }
+                else {
+                    Some(flow[e].clone())
+                }
+            };
+            let mut delta = residual(a, state[a] == STATE_LOWER, &flow);
+            let mut leave_side = 0;
+            let mut leave_node = usize::MAX;
+            let mut w = first;
+            while w != apex {
+                let e = pred[w];
+                let r = residual(e, src[e] == parent[w], &flow);
+                if less_cap(&r, &delta) {
+                    delta = r;
+                    leave_side = 1;
+                    leave_node = w;
+                }
+                w = parent[w];
+            }
+            let mut w = second;
+            while w != apex {
+                let e = pred[w];
+                let r = residual(e, src[e] == w, &flow);
+                if !less_cap(&delta, &r) {
+                    delta = r;
+                    leave_side = 2;
+                    leave_node = w;
+                }
+                w = parent[w];
+            }
+            let delta = match delta {
+                Some(x) => x,
+                None => break
+            };
+
+            // 沿环增广
+            if delta != T::default() {
+                if state[a] == STATE_LOWER {
+                    flow[a] = flow[a].clone() + delta.clone();
+                }
+                else {
+                    flow[a] = flow[a].clone() - delta.clone();
+                }
+                let mut w = first;
+                while w != apex {
+                    let e = pred[w];
+                    if src[e] == parent[w] {
+                        flow[e] = flow[e].clone() + delta.clone();
+                    }
+                    else {
+                        flow[e] = flow[e].clone() - delta.clone();
+                    }
+                    w = parent[w];
+                }
+                let mut w = second;
+                while w != apex {
+                    let e = pred[w];
+                    if src[e] == w {
+                        flow[e] = flow[e].clone() + delta.clone();
+                    }
+                    else {
+                        flow[e] = flow[e].clone() - delta.clone();
+                    }
+                    w = parent[w];
+                }
+            }
+
+            if leave_side == 0 {
+                state[a] = if state[a] == STATE_LOWER { STATE_UPPER } else { STATE_LOWER };
+                continue;
+            }
+
+            // 更新生成树
+            let la = pred[leave_node];
+            state[la] = if flow[la] == T::default() { STATE_LOWER } else { STATE_UPPER };
+            state[a] = STATE_TREE;
+            for side in 0..2 {
+                let v = if side == 0 { src[la] } else { tgt[la] };
+                let p = pos[la][side];
+                adj[v].swap_remove(p);
+                if p < adj[v].len() {
+                    let moved = adj[v][p];
+                    if src[moved] == v {
+                        pos[moved][0] = p;
+                    }
+                    else {
+                        pos[moved][1] = p;
+                    }
+                }
+            }
+            pos[a] = [adj[src[a]].len(), adj[tgt[a]].len()];
+            adj[src[a]].push(a);
+            adj[tgt[a]].push(a);
+            let (x, y) = if leave_side == 1 { (first, second) } else { (second, first) };
+            let mut stack = vec![(x, y, a)];
+            while let Some((now, p, e)) = stack.pop() {
+                parent[now] = p;
+                pred[now] = e;
+                depth[now] = depth[p] + 1;
+                pi[now] = if src[e] == p { pi[p].add(&cost[e]) } else { pi[p].sub(&cost[e]) };
+                for &e2 in &adj[now] {
+                    if e2 != e {
+                        let x = if src[e2] == now { tgt[e2] } else { src[e2] };
+                        stack.push((x, now, e2));
+                    }
+                }
+            }
+        }
+
+        // 先检查供需是否都被满足，无解时不修改残量网络
+        let mut unsatisfied = vec![];
+        for v in 0..n {
+            if flow[m + v] != T::default() {
+                unsatisfied.push(v);
+            }
+        }
+        if !unsatisfied.is_empty() {
+            return Err(FlowError::Infeasible(unsatisfied));
+        }
+        // 将流量写回残量网络
+        let mut res = E::default();
+        for a in 0..m {
+            let e = index[a];
+            let opp = self.edges[e].opp_edge;
+            self.edges[e].weight = cap[a].clone().unwrap_or_default() - flow[a].clone();
+            self.edges[opp].weight = flow[a].clone();
+            res = res + M::mul(&flow[a], &self.edges[e].cost);
+        }
+        let h : Vec<E> = pi[..n].iter().map(|x| x.small.clone()).collect();
+        if pi[..n].iter().all(|x| x.big == pi[0].big) {
+            Ok((res, h))
+        }
+        else {
+            Ok((res, self.residual_potentials(h)))
+        }
+    }
+
+    /// 按照每个点设置的供给量和需求量求最小费用流
+    ///
+    /// 供需通过set_supply、set_demand等函数设置，使用网络单纯形法求解。
+    /// 如果存在可行解，返回最小的总费用，每条边上的流量保存在残量网络中；
+    /// 否则返回FlowError::Infeasible以及供需无法被满足的点，残量网络保持不变。
+    ///
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// use network_flow::error::FlowError;
+    /// let mut g = Graph::<&str, i32, i32>::new();
+    /// g.set_supply_by_label(&"factory", &3).unwrap();
+    /// g.set_demand_by_label(&"shop", &3).unwrap();
+    /// g.add_node(&"depot");
+    /// g.add_edge2(0, 2, &2, &1).unwrap();
+    /// g.add_edge2(2, 1, &3, &1).unwrap();
+    /// g.add_edge2(0, 1, &2, &5).unwrap();
+    /// assert_eq!(g.min_cost_flow(), Ok(9));
+    /// g.set_demand(1, &5).unwrap();
+    /// assert_eq!(g.min_cost_flow(), Err(FlowError::Infeasible(vec![1])));
+    /// ```
+    pub fn min_cost_flow(&mut self) -> Result<E, FlowError> {
+        let supply = self.supply.clone();
+        let demand = self.demand.clone();
+        self.network_simplex(&supply, &demand).map(|(cost, _)| cost)
+    }
+}
@@ -0,0 +1,160 @@
+//! 原始对偶算法求最小费用最大流
+//!
+//! 开始时用spfa求出从源点出发的最短路作为每个点的势，
+//! 之后每一轮都在约化费用c(u, v) + h(u) - h(v)上使用Dijkstra求最短路，并更新势。
+
+use super::Graph;
+use crate::error::FlowError;
+use core::ops::Add;
+use core::ops::Sub;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+// 堆中的元素，距离小的先弹出
+struct State<E>(E, usize);
+
+impl<E : PartialOrd> PartialEq for State<E> {
+    fn eq(&self, other : &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<E : PartialOrd> Eq for State<E> {}
+
+impl<E : PartialOrd> PartialOrd for State<E> {
+    fn partial_cmp(&self, other : &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<E : PartialOrd> Ord for State<E> {
+    fn cmp(&self, other : &Self) -> Ordering {
+        other.0.partial_cmp(&self.0).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<L, T, E, M> Graph<L, T, E, M>
+    where
+        L : Clone + Hash + Eq,
+        E : Clone + Default + Add<Output = E> + Sub<Output = E> + PartialEq + PartialOrd,
+        T : Clone + Default + Add<Output = T> + Sub<Output = T> + PartialEq + PartialOrd,
+        M : crate::costtype::MulTE<T, E> {
+
+    // 用spfa求从s出发到每个点的最短路，作为初始的势，遇到负环时返回环上的点
+    fn initial_potentials(&self, s : usize) -> Result<Vec<E>, FlowError> {
+        let n = self.labels.len();
+        let mut h = vec![E::default(); n];
+        let mut vis = vec![false; n];
+        let mut inque = vec![false; n];
+        let mut pre = vec![usize::MAX; n];
+        let mut cnt = vec![0usize; n];
+        let mut q = VecDeque::new();
+        q.push_back(s);
+        vis[s] = true;
+        inque[s] = true;
+        while let Some(now) = q.pop_front() {
+            inque[now] = false;
+            let mut e = self.first[now].next_edge;
+            while e != usize::MAX {
+                let x = self.edges[e].to;
+                if self.edges[e].weight != T::default() {
+                    let d = h[now].clone() + self.arc_cost(e);
+                    if !vis[x] || d < h[x] {
+                        vis[x] = true;
+                        h[x] = d;
+                        pre[x] = e;
+                        cnt[x] = cnt[now] + 1;
+                        if cnt[x].is_multiple_of(n) {
+                            if let Some(cycle) = self.find_pre_cycle(&pre, true) {
+                                return Err(FlowError::NegativeCycle(cycle.into_iter().map(|e| self.edges[e].from).collect()));
+                            }
+                        }
+                        if !inque[x] {
+                            inque[x] = true;
+                            q.push_back(x);
+                        }
+                    }
+                }
+                e = self.edges[e].next_edge;
+            }
+        }
+        Ok(h)
+    }
+
+    // 在约化费用上求从s出发的最短路，pre记录最短路树上到达每个点的边
+    fn dijkstra(&self, s : usize, h : &[E], dist : &mut [E], reach : &mut [bool], pre : &mut [usize]) {
+        let n = self.labels.len();
+        let mut done = vec![false; n];
+        reach.fill(false);
+        reach[s] = true;
+        dist[s] = E::default();
+        let mut heap = BinaryHeap::new();
+        heap.push(State(E::default(), s));
+        while let Some(State(d, now)) = heap.pop() {
+            if done[now] {
+                continue;
+            }
+            done[now] = true;
+            let mut e = self.first[now].next_edge;
+            while e != usize::MAX {
+                let x = self.edges[e].to;
+                if self.edges[e].weight != T::default() && !done[x] {
+                    let nd = d.clone() + self.arc_cost(e) + h[now].clone() - h[x].clone();
+                    if !reach[x] || nd < dist[x] {
+                        reach[x] = true;
+                        dist[x] = nd.clone();
+                        pre[x] = e;
+                        heap.push(State(nd, x));
+                    }
+                }
+                e = self.edges[e].next_edge;
+            }
+        }
+    }
+
+    pub(super) fn primal_dual(&mut self, s : usize, t : usize) -> Result<(T, E), FlowError> {
+        self.check_node(s)?;
+        self.check_node(t)?;
+        let n = self.labels.len();
+        let mut flow = T::default();
+        let mut cost = E::default();
+        if s == t {
+            return Ok((flow, cost));
+        }
+        let mut h = self.initial_potentials(s)?;
+        let mut dist = vec![E::default(); n];
+        let mut reach = vec![false; n];
+        let mut pre = vec![usize::MAX; n];
+        loop {
+            self.dijkstra(s, &h, &mut dist, &mut reach, &mut pre);
+            if !reach[t] {
+                break;
+            }
+            for i in 0..n {
+                if reach[i] {
+                    h[i] = h[i].clone() + dist[i].clone();
+                }
+            }
+            let mut f = self.edges[pre[t]].weight.clone();
+            let mut now = t;
+            while now != s {
+                let e = pre[now];
+                if self.edges[e].weight < f {
+                    f = self.edges[e].weight.clone();
+                }
+                now = self.edges[e].from;
+            }
+            let mut now = t;
+            while now != s {
+                let e = pre[now];
+                self.push_flow(e, f.clone());
+                cost = cost + M::mul(&f, &self.arc_cost(e));
+                now = self.edges[e].from;
+            }
+            flow = flow + f;
+        }
+        Ok((flow, cost))
+    }
+}
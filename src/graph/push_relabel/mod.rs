@@ -0,0 +1,206 @@
+//! 最高标号预流推进(HLPP)算法
+//!
+//! 直接在Graph的残量网络(edges和first)上进行计算，结束后残量网络中为一个合法的最大流，
+//! 因此之后仍然可以调用get_cut求最小割。
+
+use super::Graph;
+use core::ops::Add;
+use core::ops::Sub;
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+impl<L, T, E, M> Graph<L, T, E, M>
+    where
+        L : Clone + Hash + Eq,
+        E : Clone + Default,
+        T : Clone + Default + Add<Output = T> + Sub<Output = T> + PartialEq + PartialOrd,
+        M : crate::costtype::MulTE<T, E> {
+
+    // 从t出发在残量网络上反向bfs，得到每个点到t的距离，作为点的高度
+    //
+    // 不经过block点向外扩展，无法到达t的点高度为inf
+    fn reverse_bfs(&self, t : usize, block : usize, height : &mut [usize], inf : usize) {
+        height.fill(inf);
+        height[t] = 0;
+        let mut q = VecDeque::new();
+        q.push_back(t);
+        while let Some(now) = q.pop_front() {
+            if now == block {
+                continue;
+            }
+            let mut e = self.first[now].next_edge;
+            while e != usize::MAX {
+                let x = self.edges[e].to;
+                let opp = self.edges[e].opp_edge;
+                if self.edges[opp].weight != T::default() && height[x] == inf {
+                    height[x] = height[now] + 1;
+                    q.push_back(x);
+                }
+                e = self.edges[e].next_edge;
+            }
+        }
+    }
+
+    pub(super) fn push_relabel(&mut self, s : usize, t : usize) -> T {
+        let n = self.labels.len();
+        if s == t {
+            return T::default();
+        }
+        let mut excess = vec![T::default(); n];
+        let mut height = vec![0; n];
+        let mut count = vec![0usize; n + 1];
+        let mut buckets = vec![vec![]; n];
+        let mut current : Vec<usize> = self.first.iter().map(|x| x.next_edge).collect();
+
+        // 将源点的所有出边推满
+        let mut e = self.first[s].next_edge;
+        while e != usize::MAX {
+            let w = self.edges[e].weight.clone();
+            if w != T::default() {
+                let x = self.edges[e].to;
+                self.push_flow(e, w.clone());
+                excess[x] = excess[x].clone() + w;
+            }
+            e = self.edges[e].next_edge;
+        }
+
+        // 第一阶段：求出最大预流，只处理高度小于n的活跃点
+        let mut highest = 0;
+        let mut relabels = n;
+        loop {
+            if relabels >= n {
+                // 全局重标号
+                relabels = 0;
+                self.reverse_bfs(t, s, &mut height, n);
+                height[s] = n;
+                count.fill(0);
+                for i in &mut buckets {
+                    i.clear();
+                }
+                highest = 0;
+                for i in 0..n {
+                    count[height[i]] += 1;
+                    current[i] = self.first[i].next_edge;
+                    if i != s && i != t && height[i] < n && excess[i] != T::default() {
+                        buckets[height[i]].push(i);
+                        highest = highest.max(height[i]);
+                    }
+                }
+            }
+            while highest > 0 && buckets[highest].is_empty() {
+                highest -= 1;
+            }
+            let now = match buckets[highest].pop() {
+                Some(x) => x,
+                None => break
+            };
+            if height[now] != highest || excess[now] == T::default() {
+                continue;
+            }
+            while excess[now] != T::default() {
+                let e = current[now];
+                if e == usize::MAX {
+                    // 重标号
+                    relabels += 1;
+                    let old = height[now];
+                    let mut h = n;
+                    let mut e = self.first[now].next_edge;
+                    while e != usize::MAX {
+                        if self.edges[e].weight != T::default() {
+                            h = h.min(height[self.edges[e].to] + 1);
+                        }
+                        e = self.edges[e].next_edge;
+                    }
+                    count[old] -= 1;
+                    if count[old] == 0 {
+                        // gap优化：高度在old之上的点都无法再到达t
+                        for i in 0..n {
+                            if height[i] > old && height[i] < n {
+                                count[height[i]] -= 1;
+                                height[i] = n;
+                                count[n] += 1;
+                            }
+                        }
+                        h = n;
+                    }
+                    height[now] = h;
+                    count[h] += 1;
+                    if h >= n {
+                        break;
+                    }
+                    current[now] = self.first[now].next_edge;
+                    continue;
+                }
+                let x = self.edges[e].to;
+                let w = self.edges[e].weight.clone();
+                if w != T::default() && height[now] == height[x] + 1 {
+                    let f = if excess[now] < w { excess[now].clone() } else { w.clone() };
+                    self.push_flow(e, f.clone());
+                    excess[now] = excess[now].clone() - f.clone();
+                    if x != s && x != t && excess[x] == T::default() {
+                        buckets[height[x]].push(x);
+                        highest = highest.max(height[x]);
+                    }
+                    excess[x] = excess[x].clone() + f.clone();
+                    if f == w {
+                        current[now] = self.edges[e].next_edge;
+                    }
+                }
+                else {
+                    current[now] = self.edges[e].next_edge;
+                }
+            }
+        }
+
+        // 第二阶段：将剩余的超额流退回源点，使预流成为合法的流
+        let inf = 2 * n + 1;
+        self.reverse_bfs(s, t, &mut height, inf);
+        height[t] = inf;
+        let mut q = VecDeque::new();
+        for i in 0..n {
+            current[i] = self.first[i].next_edge;
+            if i != s && i != t && excess[i] != T::default() {
+                q.push_back(i);
+            }
+        }
+        while let Some(now) = q.pop_front() {
+            while excess[now] != T::default() {
+                let e = current[now];
+                if e == usize::MAX {
+                    let mut h = usize::MAX;
+                    let mut e = self.first[now].next_edge;
+                    while e != usize::MAX {
+                        if self.edges[e].weight != T::default() {
+                            h = h.min(height[self.edges[e].to].saturating_add(1));
+                        }
+                        e = self.edges[e].next_edge;
+                    }
+                    if h == usize::MAX {
+                        break;
+                    }
+                    height[now] = h;
+                    current[now] = self.first[now].next_edge;
+                    continue;
+                }
+                let x = self.edges[e].to;
+                let w = self.edges[e].weight.clone();
+                if w != T::default() && height[now] == height[x].saturating_add(1) {
+                    let f = if excess[now] < w { excess[now].clone() } else { w.clone() };
+                    self.push_flow(e, f.clone());
+                    excess[now] = excess[now].clone() - f.clone();
+                    if x != s && x != t && excess[x] == T::default() {
+                        q.push_back(x);
+                    }
+                    excess[x] = excess[x].clone() + f.clone();
+                    if f == w {
+                        current[now] = self.edges[e].next_edge;
+                    }
+                }
+                else {
+                    current[now] = self.edges[e].next_edge;
+                }
+            }
+        }
+        excess[t].clone()
+    }
+}
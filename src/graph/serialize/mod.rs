@@ -0,0 +1,91 @@
+//! 开启serde feature时图的序列化和反序列化
+//!
+//! 图被表示为点和边的列表，而不是内部的邻接表：每个点包含标签、供给和需求，
+//! 每条边包含起点、终点、容量上界、下界、费用和当前的流量，反向边不会出现。
+//! 反序列化时供给、需求、下界和费用缺省为0，流量缺省为下界。
+
+use super::Graph;
+use crate::costtype::MulTE;
+use core::ops::Add;
+use core::ops::Sub;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::hash::Hash;
+
+#[derive(Serialize, Deserialize)]
+#[serde(bound(deserialize = "L : Deserialize<'de>, T : Deserialize<'de> + Default"))]
+struct Node<L, T> {
+    label : L,
+    #[serde(default)]
+    supply : T,
+    #[serde(default)]
+    demand : T
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(bound(deserialize = "T : Deserialize<'de> + Default, E : Deserialize<'de> + Default"))]
+struct Arc<T, E> {
+    from : usize,
+    to : usize,
+    capacity : T,
+    #[serde(default)]
+    lower : T,
+    #[serde(default)]
+    cost : E,
+    flow : Option<T>
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Graph")]
+#[serde(bound(deserialize = "L : Deserialize<'de>, T : Deserialize<'de> + Default, E : Deserialize<'de> + Default"))]
+struct Repr<L, T, E> {
+    nodes : Vec<Node<L, T>>,
+    edges : Vec<Arc<T, E>>
+}
+
+impl<L, T, E, M> Serialize for Graph<L, T, E, M>
+    where
+        L : Serialize + Clone + Hash + Eq,
+        T : Serialize + Clone + Default + Add<Output = T>,
+        E : Serialize + Clone + Default,
+        M : MulTE<T, E> {
+    fn serialize<S : Serializer>(&self, serializer : S) -> Result<S::Ok, S::Error> {
+        let nodes = (0..self.node_count()).map(|i| Node {
+            label : self.labels[i].clone(),
+            supply : self.node_supply(i),
+            demand : self.node_demand(i)
+        }).collect();
+        let edges = self.edges_with_flow().map(|(id, from, to, flow)| Arc {
+            from,
+            to,
+            capacity : self.edge_capacity(id.0),
+            lower : self.edges[id.0].lower.clone(),
+            cost : self.edges[id.0].cost.clone(),
+            flow : Some(flow)
+        }).collect();
+        Repr { nodes, edges }.serialize(serializer)
+    }
+}
+
+impl<'de, L, T, E, M> Deserialize<'de> for Graph<L, T, E, M>
+    where
+        L : Deserialize<'de> + Clone + Hash + Eq,
+        T : Deserialize<'de> + Clone + Default + Add<Output = T> + Sub<Output = T> + PartialOrd,
+        E : Deserialize<'de> + Clone + Default,
+        M : MulTE<T, E> {
+    fn deserialize<D : Deserializer<'de>>(deserializer : D) -> Result<Self, D::Error> {
+        let repr = Repr::<L, T, E>::deserialize(deserializer)?;
+        let labels : Vec<L> = repr.nodes.iter().map(|x| x.label.clone()).collect();
+        let mut g = Self::create_graph(&labels);
+        if g.hs.len() != labels.len() {
+            return Err(D::Error::custom("duplicate node label"));
+        }
+        (g.supply, g.demand) = repr.nodes.into_iter().map(|x| (x.supply, x.demand)).unzip();
+        for arc in repr.edges {
+            let flow = arc.flow.unwrap_or_else(|| arc.lower.clone());
+            g.add_edge_with_flow(arc.from, arc.to, &arc.lower, &arc.capacity, &arc.cost, &flow)
+                .map_err(D::Error::custom)?;
+        }
+        Ok(g)
+    }
+}
@@ -0,0 +1,113 @@
+//! 保存和恢复图中的流量状态
+//!
+//! 快照记录每条边的端点、是否已被删除、残量、没有流量时的残量、费用和下界，以及每个点的供给和需求，
+//! 不复制标签，因此适合在同一个图上反复尝试删除边、修改容量或费用、重新求解之后再回滚。
+//! 需要回滚增删点的修改时应当使用clone复制整个图。
+
+use super::{Edge, EdgeId, Graph};
+use crate::error::FlowError;
+use std::hash::Hash;
+
+/// 图中流量状态的快照，由Graph::snapshot得到
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowSnapshot<T, E> {
+    nodes : usize,
+    ends : Vec<(usize, usize)>,
+    removed : Vec<bool>,
+    weights : Vec<T>,
+    capacities : Vec<T>,
+    costs : Vec<E>,
+    lowers : Vec<T>,
+    supply : Vec<T>,
+    demand : Vec<T>
+}
+
+impl<L, T, E, M> Graph<L, T, E, M>
+    where
+        L : Hash,
+        E : Clone,
+        T : Clone,
+        M : crate::costtype::MulTE<T, E> {
+
+    /// 记录当前每条边的端点和是否已被删除，边上的流量、容量、费用和下界，以及每个点的供给和需求
+    ///
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// let mut g = Graph::<usize, u32>::create_graph(&[0, 1, 2]);
+    /// let a = g.add_edge(0, 1, &3).unwrap();
+    /// g.add_edge(1, 2, &2).unwrap();
+    /// g.add_edge(0, 2, &1).unwrap();
+    /// assert_eq!(g.get_max_flow(0, 2), Ok(3));
+    /// let saved = g.snapshot();
+    /// g.remove_edge(a).unwrap();
+    /// assert_eq!(g.max_flow_warm(0, 2), Ok(1));
+    /// g.restore(&saved).unwrap();
+    /// assert_eq!((g.get_flow(a), g.get_capacity(a)), (Ok(2), Ok(3)));
+    /// assert_eq!(g.max_flow_warm(0, 2), Ok(3));
+    /// ```
+    pub fn snapshot(&self) -> FlowSnapshot<T, E> {
+        FlowSnapshot {
+            nodes : self.labels.len(),
+            ends : self.edges.iter().map(|e| (e.from, e.to)).collect(),
+            removed : (0..self.edges.len()).map(|i| self.is_removed(i)).collect(),
+            weights : self.edges.iter().map(|e| e.weight.clone()).collect(),
+            capacities : self.edges.iter().map(|e| e.capacity.clone()).collect(),
+            costs : self.edges.iter().map(|e| e.cost.clone()).collect(),
+            lowers : self.edges.iter().map(|e| e.lower.clone()).collect(),
+            supply : self.supply.clone(),
+            demand : self.demand.clone()
+        }
+    }
+
+    /// 将图中的边、每条边上的流量、容量、费用和下界，以及每个点的供给和需求恢复为快照中的值
+    ///
+    /// 快照之后删除的边会被恢复，添加的边会被删除，恢复的边的编号与快照时相同。
+    /// 也可以恢复到同一个图之后的快照，此时快照中多出的边会被重新添加。
+    /// 快照之后点数发生了变化，或者快照中的某条边的端点已经不同(如删除了与其相连的点)时
+    /// 返回FlowError::UnsupportedGraph，此时图不会被修改
+    pub fn restore(&mut self, snapshot : &FlowSnapshot<T, E>) -> Result<(), FlowError> {
+        let m = snapshot.ends.len();
+        let k = m.min(self.edges.len());
+        if snapshot.nodes != self.labels.len() || !self.edges[..k].iter().map(|e| (e.from, e.to)).eq(snapshot.ends[..k].iter().copied()) {
+            return Err(FlowError::UnsupportedGraph(String::from("graph structure changed since snapshot")));
+        }
+        // 快照中比当前多出的边先作为已被删除的边加入，之后与其他边一起恢复
+        for i in k..m {
+            let (from, to) = snapshot.ends[i];
+            self.edges.push(Edge {
+                from, to,
+                next_edge : usize::MAX,
+                opp_edge : i ^ 1,
+                weight : snapshot.weights[i].clone(),
+                capacity : snapshot.capacities[i].clone(),
+                cost : snapshot.costs[i].clone(),
+                reversed : true,
+                lower : snapshot.lowers[i].clone()
+            });
+        }
+        for i in (0..self.edges.len()).step_by(2) {
+            let removed = i >= m || snapshot.removed[i];
+            if removed && !self.is_removed(i) {
+                self.remove_edge(EdgeId(i))?;
+            }
+            else if !removed && self.is_removed(i) {
+                self.edges[i].reversed = false;
+                if self.edges[i].from != self.edges[i].to {
+                    self.link(i);
+                }
+                self.link(i + 1);
+            }
+        }
+        self.edges.truncate(m);
+        for (i, e) in self.edges.iter_mut().enumerate() {
+            e.weight = snapshot.weights[i].clone();
+            e.capacity = snapshot.capacities[i].clone();
+            e.cost = snapshot.costs[i].clone();
+            e.lower = snapshot.lowers[i].clone();
+        }
+        self.supply = snapshot.supply.clone();
+        self.demand = snapshot.demand.clone();
+        Ok(())
+    }
+}
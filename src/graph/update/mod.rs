@@ -0,0 +1,197 @@
+//! 修改已有的边的容量和费用，并在原有的流的基础上继续求解
+//!
+//! 容量减小到当前的流量以下时，边上多出的流量d使起点u的流入比流出多d，终点v的流出比流入少d。
+//! 修复时先在残量网络中寻找从u到v的路径绕开这条边，剩下的部分沿残量网络从u退回到
+//! 流出大于流入的点(源点)，并从流入大于流出的点(汇点)补到v，这样其他点上的流量仍然守恒。
+
+use super::Graph;
+use super::edge::EdgeId;
+use crate::error::FlowError;
+use core::ops::Add;
+use core::ops::Sub;
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+impl<L, T, E, M> Graph<L, T, E, M>
+    where
+        L : Clone + Hash + Eq,
+        E : Clone + Default,
+        T : Clone + Default + Add<Output = T> + Sub<Output = T> + PartialEq + PartialOrd,
+        M : crate::costtype::MulTE<T, E> {
+
+    // 每个点流出和流入的流量
+    fn node_flows(&self) -> (Vec<T>, Vec<T>) {
+        let n = self.labels.len();
+        let mut outflow = vec![T::default(); n];
+        let mut inflow = vec![T::default(); n];
+        for (i, e) in self.edges.iter().enumerate() {
+            if !e.reversed {
+                let f = self.edge_flow(i);
+                outflow[e.from] = outflow[e.from].clone() + f.clone();
+                inflow[e.to] = inflow[e.to].clone() + f;
+            }
+        }
+        (outflow, inflow)
+    }
+
+    // 在残量网络中BFS寻找从s到满足target的点的路径，按照残量边的方向返回路径上的边
+    //
+    // forward为false时沿残量边的反方向搜索，即寻找从满足target的点到s的路径
+    fn residual_path(&self, s : usize, target : impl Fn(usize) -> bool, forward : bool) -> Option<Vec<usize>> {
+        let mut pre = vec![usize::MAX; self.labels.len()];
+        let mut visited = vec![false; self.labels.len()];
+        let mut q = VecDeque::new();
+        visited[s] = true;
+        q.push_back(s);
+        while let Some(now) = q.pop_front() {
+            if target(now) {
+                let mut res = vec![];
+                let mut x = now;
+                while x != s {
+                    let e = pre[x];
+                    res.push(e);
+                    x = if forward { self.edges[e].from } else { self.edges[e].to };
+                }
+                if forward {
+                    res.reverse();
+                }
+                return Some(res);
+            }
+            let mut e = self.first[now].next_edge;
+            while e != usize::MAX {
+                let arc = if forward { e } else { self.edges[e].opp_edge };
+                let x = self.edges[e].to;
+                if self.edges[arc].weight != T::default() && !visited[x] {
+                    visited[x] = true;
+                    pre[x] = arc;
+                    q.push_back(x);
+                }
+                e = self.edges[e].next_edge;
+            }
+        }
+        None
+    }
+
+    // 沿路径推送不超过limit的流量，返回推送的流量
+    fn augment_path(&mut self, path : &[usize], limit : T) -> T {
+        let mut f = limit;
+        for &e in path {
+            if self.edges[e].weight < f {
+                f = self.edges[e].weight.clone();
+            }
+        }
+        for &e in path {
+            self.push_flow(e, f.clone());
+        }
+        f
+    }
+
+    // 边(u, v)上的流量减少了d之后恢复其他点上的流量守恒，outflow和inflow为减少之前的值
+    fn repair_flow(&mut self, u : usize, v : usize, d : T, outflow : Vec<T>, inflow : Vec<T>) -> Result<(), FlowError> {
+        let zero = T::default();
+        let mut rest = d;
+        while rest > zero {
+            let Some(path) = self.residual_path(u, |x| x == v, true) else { break };
+            let f = self.augment_path(&path, rest.clone());
+            rest = rest - f;
+        }
+        let mut surplus : Vec<T> = outflow.iter().zip(&inflow)
+            .map(|(a, b)| if a > b { a.clone() - b.clone() } else { T::default() }).collect();
+        let mut deficit : Vec<T> = outflow.iter().zip(&inflow)
+            .map(|(a, b)| if b > a { b.clone() - a.clone() } else { T::default() }).collect();
+        for (start, balance, forward) in [(u, &mut surplus, true), (v, &mut deficit, false)] {
+            let mut left = rest.clone();
+            while left > zero {
+                let path = self.residual_path(start, |x| balance[x] > zero, forward)
+                    .ok_or_else(|| FlowError::Infeasible(vec![u, v]))?;
+                let w = match path.first() {
+                    None => start,
+                    Some(_) if forward => self.edges[path[path.len() - 1]].to,
+                    Some(&e) => self.edges[e].from
+                };
+                let limit = if balance[w] < left { balance[w].clone() } else { left.clone() };
+                let f = self.augment_path(&path, limit);
+                balance[w] = balance[w].clone() - f.clone();
+                left = left - f;
+            }
+        }
+        Ok(())
+    }
+
+    /// 修改边的容量，尽量保留当前的流
+    ///
+    /// 新的容量不小于边上的流量时只改变剩余容量；否则边上的流量减少到新的容量，
+    /// 并通过绕开这条边或者减少源点流出的流量使其他点上的流量仍然守恒，之后可以用max_flow_warm继续求解。
+    ///
+    /// 容量小于下界或者流量无法修复时返回FlowError::Infeasible，附带边的两个端点，此时图不会被修改。
+    /// id不是图中正向边的编号时返回FlowError::InvalidEdge
+    ///
+    /// ```
+    /// use network_flow::graph::Graph;
+    /// let mut g = Graph::<usize, u32>::create_graph(&[0, 1, 2, 3]);
+    /// let a = g.add_edge(0, 1, &3).unwrap();
+    /// g.add_edge(1, 3, &3).unwrap();
+    /// g.add_edge(0, 2, &2).unwrap();
+    /// g.add_edge(2, 3, &1).unwrap();
+    /// g.add_edge(2, 1, &2).unwrap();
+    /// assert_eq!(g.get_max_flow(0, 3), Ok(4));
+    /// g.set_capacity(a, &1).unwrap();
+    /// assert_eq!(g.get_flow(a), Ok(1));
+    /// assert_eq!(g.max_flow_warm(0, 3), Ok(3));
+    /// ```
+    pub fn set_capacity(&mut self, id : EdgeId, capacity : &T) -> Result<(), FlowError> {
+        self.check_edge(id)?;
+        let i = id.0;
+        let (u, v) = (self.edges[i].from, self.edges[i].to);
+        let lower = self.edges[i].lower.clone();
+        if *capacity < lower {
+            return Err(FlowError::Infeasible(vec![u, v]));
+        }
+        let flow = self.edge_flow(i);
+        if flow <= *capacity {
+            self.edges[i].weight = capacity.clone() - flow;
+            self.edges[i].capacity = capacity.clone() - lower;
+            return Ok(());
+        }
+        let saved : Vec<T> = self.edges.iter().map(|e| e.weight.clone()).collect();
+        let (outflow, inflow) = self.node_flows();
+        self.edges[i].weight = T::default();
+        self.edges[i + 1].weight = capacity.clone() - lower.clone();
+        if u != v {
+            if let Err(e) = self.repair_flow(u, v, flow - capacity.clone(), outflow, inflow) {
+                for (edge, w) in self.edges.iter_mut().zip(saved) {
+                    edge.weight = w;
+                }
+                return Err(e);
+            }
+        }
+        self.edges[i].capacity = capacity.clone() - lower;
+        Ok(())
+    }
+
+    /// 修改边的费用，边上的流量不变
+    ///
+    /// 修改之后当前的流可能不再是费用最小的，可以使用McmfAlgorithm::CycleCanceling在此基础上继续求解。
+    /// id不是图中正向边的编号时返回FlowError::InvalidEdge
+    pub fn set_cost(&mut self, id : EdgeId, cost : &E) -> Result<(), FlowError> {
+        self.check_edge(id)?;
+        self.edges[id.0].cost = cost.clone();
+        self.edges[id.0 + 1].cost = cost.clone();
+        Ok(())
+    }
+
+    /// 在当前的流的基础上继续求从s到t的最大流，返回s流出的总流量
+    ///
+    /// get_max_flow返回的是新增的流量，而这里的返回值包括之前已有的流量，
+    /// 适合在set_capacity等修改之后重新求解，不需要从零流开始。边上的流量与get_flow相同，包括下界的部分。
+    pub fn max_flow_warm(&mut self, s : usize, t : usize) -> Result<T, FlowError> {
+        self.get_max_flow(s, t)?;
+        let (outflow, inflow) = self.node_flows();
+        if outflow[s] > inflow[s] {
+            Ok(outflow[s].clone() - inflow[s].clone())
+        }
+        else {
+            Ok(T::default())
+        }
+    }
+}
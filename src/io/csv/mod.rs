@@ -0,0 +1,264 @@
+//! CSV形式的边表的输入输出
+//!
+//! 每一行为一条边，包含起点和终点的标签、容量，以及可选的费用、下界和流量。
+//! 列可以通过表头中的名称或者从0开始的位置指定，带引号的字段中可以包含分隔符，
+//! 引号本身用两个引号表示。每行恰好是一条边，因此字段中不能包含换行。
+
+use crate::costtype::MulTE;
+use crate::error::FlowError;
+use crate::graph::Graph;
+use crate::graph::edge::EdgeId;
+use super::{StrIO, TextEdge};
+use core::ops::Add;
+use core::ops::Sub;
+use std::hash::Hash;
+use std::io::{BufRead, BufReader, Read, Write};
+
+/// CSV文件中的一列
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CsvColumn {
+    /// 从0开始的位置
+    Index(usize),
+    /// 表头中的名称，只能在有表头时使用
+    Name(String)
+}
+
+/// CSV文件的格式和各列的含义
+///
+/// 默认的格式以`,`分隔，第一行为表头，列名为`from`、`to`、`capacity`、`cost`、`lower`和`flow`，
+/// 与write_csv输出的格式相同。按名称指定的费用、下界和流量在表头中不存在时被忽略。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvFormat {
+    /// 分隔符
+    pub delimiter : char,
+    /// 第一行是否为表头
+    pub has_header : bool,
+    /// 起点的标签所在的列
+    pub from : CsvColumn,
+    /// 终点的标签所在的列
+    pub to : CsvColumn,
+    /// 容量所在的列
+    pub capacity : CsvColumn,
+    /// 费用所在的列，缺省为0
+    pub cost : Option<CsvColumn>,
+    /// 下界所在的列，缺省为0
+    pub lower : Option<CsvColumn>,
+    /// 已有的流量所在的列，缺省为下界
+    pub flow : Option<CsvColumn>
+}
+
+impl Default for CsvFormat {
+    fn default() -> Self {
+        let name = |s : &str| CsvColumn::Name(String::from(s));
+        CsvFormat {
+            delimiter : ',',
+            has_header : true,
+            from : name("from"),
+            to : name("to"),
+            capacity : name("capacity"),
+            cost : Some(name("cost")),
+            lower : Some(name("lower")),
+            flow : Some(name("flow"))
+        }
+    }
+}
+
+impl CsvFormat {
+    /// 没有表头、依次为起点、终点、容量和费用的格式
+    pub fn without_header() -> Self {
+        CsvFormat {
+            delimiter : ',',
+            has_header : false,
+            from : CsvColumn::Index(0),
+            to : CsvColumn::Index(1),
+            capacity : CsvColumn::Index(2),
+            cost : Some(CsvColumn::Index(3)),
+            lower : None,
+            flow : None
+        }
+    }
+}
+
+// 将一行拆分为字段，不带引号的字段会去掉两端的空白
+fn split_row(line : &str, delimiter : char) -> Result<Vec<String>, FlowError> {
+    let mut res = vec![];
+    let mut chars = line.chars().peekable();
+    loop {
+        let mut field = String::new();
+        while chars.peek().is_some_and(|c| *c != delimiter && c.is_whitespace()) {
+            chars.next();
+        }
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('"') if chars.peek() == Some(&'"') => { chars.next(); field.push('"'); },
+                    Some('"') => break,
+                    Some(c) => field.push(c),
+                    None => return Err(FlowError::MalformedFile(String::from("unterminated quote")))
+                }
+            }
+            while chars.peek().is_some_and(|c| *c != delimiter) {
+                if !chars.next().unwrap().is_whitespace() {
+                    return Err(FlowError::MalformedFile(String::from("text after closing quote")));
+                }
+            }
+        }
+        else {
+            while let Some(c) = chars.next_if(|c| *c != delimiter) {
+                field.push(c);
+            }
+            field = field.trim_end().to_string();
+        }
+        res.push(field);
+        if chars.next().is_none() {
+            return Ok(res);
+        }
+    }
+}
+
+// 在需要时给字段加上引号，包含换行的字段无法按行读回
+fn quote(s : &str, delimiter : char) -> Result<String, FlowError> {
+    if s.contains(['\n', '\r']) {
+        Err(FlowError::MalformedFile(String::from("line break in field")))
+    }
+    else if s.contains(delimiter) || s.contains('"') || s.trim() != s {
+        Ok(format!("\"{}\"", s.replace('"', "\"\"")))
+    }
+    else {
+        Ok(s.to_string())
+    }
+}
+
+// 确定列的位置，按名称指定且表头中不存在时为None
+fn resolve(column : &CsvColumn, header : Option<&[String]>) -> Result<Option<usize>, FlowError> {
+    match (column, header) {
+        (CsvColumn::Index(i), _) => Ok(Some(*i)),
+        (CsvColumn::Name(name), Some(header)) => Ok(header.iter().position(|x| x == name)),
+        (CsvColumn::Name(_), None) => Err(FlowError::MalformedFile(String::from("column name without header")))
+    }
+}
+
+fn required(column : &CsvColumn, header : Option<&[String]>) -> Result<usize, FlowError> {
+    resolve(column, header)?.ok_or_else(|| match column {
+        CsvColumn::Name(name) => FlowError::MalformedFile(format!("missing column {}", name)),
+        CsvColumn::Index(i) => FlowError::MalformedFile(format!("missing column {}", i))
+    })
+}
+
+/// 从r中读取CSV形式的边表，将其中的边添加到g中，返回添加的边的编号
+///
+/// 点的标签通过StrIO解析，已经在图中的标签对应原有的点，其他标签按照出现的顺序添加为新的点。
+/// 空行会被忽略。某一行(从1开始计数，包括表头)不合法时返回FlowError::Line以及行号和具体的错误，
+/// 此时之前的行已经被添加到图中，而出错的行中新添加的点会被删除。
+///
+/// ```
+/// use network_flow::graph::Graph;
+/// use network_flow::io::csv::{read_csv, CsvColumn, CsvFormat};
+/// let mut g = Graph::<String, u32, i32>::new();
+/// let format = CsvFormat { capacity : CsvColumn::Name(String::from("cap")), ..CsvFormat::default() };
+/// let s = "to,from,cap,cost\nt,s,3,2\nt,\"a, b\",2,1\n\"a, b\",s,1,1\n";
+/// let edges = read_csv(&mut g, s.as_bytes(), &format).unwrap();
+/// assert_eq!(edges.len(), 3);
+/// assert_eq!(g.get_index(&String::from("a, b")), Some(2));
+/// assert_eq!(g.mcmf(0, 1), Ok((4, 8)));
+/// ```
+pub fn read_csv<L, T, E, M, R>(g : &mut Graph<L, T, E, M>, r : R, format : &CsvFormat) -> Result<Vec<EdgeId>, FlowError>
+    where
+        L : StrIO + Clone + Hash + Eq,
+        T : StrIO + Clone + Default + Sub<Output = T> + PartialOrd,
+        E : StrIO + Clone + Default,
+        M : MulTE<T, E>,
+        R : Read {
+    let mut lines = BufReader::new(r).lines().enumerate();
+    let mut header = None;
+    if format.has_header {
+        if let Some((_, line)) = lines.next() {
+            header = Some(split_row(&line?, format.delimiter).map_err(|e| FlowError::Line(1, Box::new(e)))?);
+        }
+    }
+    let columns = (|| {
+        let header = header.as_deref();
+        Ok::<_, FlowError>((
+            required(&format.from, header)?,
+            required(&format.to, header)?,
+            required(&format.capacity, header)?,
+            format.cost.as_ref().map(|x| resolve(x, header)).transpose()?.flatten(),
+            format.lower.as_ref().map(|x| resolve(x, header)).transpose()?.flatten(),
+            format.flow.as_ref().map(|x| resolve(x, header)).transpose()?.flatten()
+        ))
+    })().map_err(|e| if format.has_header { FlowError::Line(1, Box::new(e)) } else { e })?;
+    let mut res = vec![];
+    for (i, line) in lines {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let count = g.node_count();
+        let row = (|| {
+            let fields = split_row(&line, format.delimiter)?;
+            let field = |x : usize| fields.get(x).map(|x| x.as_str())
+                .ok_or_else(|| FlowError::MalformedFile(format!("missing field {}", x)));
+            let optional = |x : Option<usize>| x.and_then(|x| fields.get(x)).map(|x| x.as_str()).filter(|x| !x.is_empty());
+            let (from, to, capacity, cost, lower, flow) = columns;
+            let mut node = |x : &str| -> Result<usize, FlowError> {
+                let label = L::from_str(x)?;
+                Ok(match g.get_index(&label) {
+                    Some(x) => x,
+                    None => {
+                        g.add_node(&label);
+                        g.node_count() - 1
+                    }
+                })
+            };
+            let (from, to) = (node(field(from)?)?, node(field(to)?)?);
+            let edge = TextEdge {
+                capacity : Some(field(capacity)?),
+                lower : optional(lower),
+                cost : optional(cost),
+                flow : optional(flow)
+            };
+            edge.add_to(g, from, to, true)
+        })();
+        match row {
+            Ok(id) => res.push(id),
+            Err(e) => {
+                // 新添加的点编号最大，删除时不会移动其他点
+                for x in (count..g.node_count()).rev() {
+                    g.remove_node(x)?;
+                }
+                return Err(FlowError::Line(i + 1, Box::new(e)));
+            }
+        }
+    }
+    Ok(res)
+}
+
+/// 将图中的边以CSV的形式写入w中
+///
+/// 只使用format中的分隔符和是否输出表头，各列依次为起点和终点的标签、容量、费用、下界和当前的流量，
+/// 表头为`from`、`to`、`capacity`、`cost`、`lower`和`flow`，可以使用默认的格式读入。
+/// 字段中包含换行时返回FlowError::MalformedFile，此时w中可能已经写入了部分的边。
+pub fn write_csv<L, T, E, M, W>(g : &Graph<L, T, E, M>, mut w : W, format : &CsvFormat) -> Result<(), FlowError>
+    where
+        L : StrIO + Clone + Hash + Eq,
+        T : StrIO + Clone + Default + Add<Output = T>,
+        E : StrIO + Clone + Default,
+        M : MulTE<T, E>,
+        W : Write {
+    let d = format.delimiter;
+    if format.has_header {
+        writeln!(w, "from{0}to{0}capacity{0}cost{0}lower{0}flow", d)?;
+    }
+    for (id, from, to, flow) in g.edges_with_flow() {
+        let label = |x : usize| quote(&g.get_label(x).map(|x| x.to_str()).unwrap_or_default(), d);
+        let values = [g.edge_capacity(id.0).to_str(), g.edges[id.0].cost.to_str(), g.edges[id.0].lower.to_str(), flow.to_str()];
+        write!(w, "{}{}{}", label(from)?, d, label(to)?)?;
+        for x in values {
+            write!(w, "{}{}", d, quote(&x, d)?)?;
+        }
+        writeln!(w)?;
+    }
+    w.flush()?;
+    Ok(())
+}
@@ -0,0 +1,266 @@
+//! DIMACS格式的最大流和最小费用流问题的输入输出
+//!
+//! 以`c`开头的行为注释，`p max 点数 边数`或者`p min 点数 边数`给出问题的类型，
+//! 点的编号从1开始。最大流问题中`n 编号 s`和`n 编号 t`给出源点和汇点，
+//! `a 起点 终点 容量`给出一条边；最小费用流问题中`n 编号 供给量`给出点的供给，
+//! 负数表示需求，`a 起点 终点 下界 容量 费用`给出一条边。
+//!
+//! 读入的图中编号为i的点的标签为i，在图中的编号为i - 1。
+//! 点在第一次被用到时才加入图中，没有用到的点在读完后补上，
+//! 因此问题行中的点数不能远大于文件中的边数和点行数。
+
+use crate::costtype::MulTE;
+use crate::error::FlowError;
+use crate::graph::Graph;
+use super::StrIO;
+use core::ops::Add;
+use core::ops::Sub;
+use std::hash::Hash;
+use std::io::{BufRead, BufReader, Read, Write};
+
+/// DIMACS文件中问题的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DimacsProblem {
+    /// `p max`，附带源点和汇点在图中的编号
+    MaxFlow { s : usize, t : usize },
+    /// `p min`，点的供给和需求保存在图中
+    MinCostFlow
+}
+
+fn field<'a>(tokens : &mut impl Iterator<Item = &'a str>) -> Result<&'a str, FlowError> {
+    tokens.next().ok_or_else(|| FlowError::MalformedFile(String::from("missing field")))
+}
+
+// 除了边和点行用到的点以外最多允许的点数
+const SPARE_NODES : usize = 1 << 16;
+
+// 读取过程中的状态
+struct State<T, E, M : MulTE<T, E>> {
+    graph : Graph<usize, T, E, M>,
+    min : bool,
+    // 问题行中给出的点数和已经读入的点行和边行数
+    n : usize,
+    lines : usize,
+    // 问题行中给出的边数和已经读入的边数
+    expected : usize,
+    arcs : usize,
+    s : Option<usize>,
+    t : Option<usize>
+}
+
+impl<T, E, M> State<T, E, M>
+    where
+        T : StrIO + Clone + Default + Sub<Output = T> + PartialOrd,
+        E : StrIO + Clone + Default,
+        M : MulTE<T, E> {
+    // 把图中的点补到x个，点数超过读入的行数所允许的范围时报错
+    fn grow(&mut self, x : usize) -> Result<(), FlowError> {
+        if x > self.lines.saturating_mul(2).saturating_add(SPARE_NODES) {
+            return Err(FlowError::MalformedFile(format!("too many nodes: {}", x)));
+        }
+        for i in self.graph.node_count() + 1..=x {
+            self.graph.add_node(&i);
+        }
+        Ok(())
+    }
+
+    // 读取一个从1开始的点的编号，返回其在图中的编号
+    fn node<'a>(&mut self, tokens : &mut impl Iterator<Item = &'a str>) -> Result<usize, FlowError> {
+        let x = usize::from_str(field(tokens)?)?;
+        if x == 0 || x > self.n {
+            return Err(FlowError::InvalidNode(x));
+        }
+        self.grow(x)?;
+        Ok(x - 1)
+    }
+
+    fn node_line<'a>(&mut self, tokens : &mut impl Iterator<Item = &'a str>) -> Result<(), FlowError> {
+        self.lines += 1;
+        let x = self.node(tokens)?;
+        let value = field(tokens)?;
+        if self.min {
+            match value.strip_prefix('-') {
+                Some(v) => self.graph.set_demand(x, &T::from_str(v)?),
+                None => self.graph.set_supply(x, &T::from_str(value)?)
+            }
+        }
+        else {
+            match value {
+                "s" => self.s = Some(x),
+                "t" => self.t = Some(x),
+                _ => return Err(FlowError::MalformedFile(format!("unknown node type {}", value)))
+            }
+            Ok(())
+        }
+    }
+
+    fn arc_line<'a>(&mut self, tokens : &mut impl Iterator<Item = &'a str>) -> Result<(), FlowError> {
+        self.lines += 1;
+        let from = self.node(tokens)?;
+        let to = self.node(tokens)?;
+        if self.min {
+            let lower = T::from_str(field(tokens)?)?;
+            let upper = T::from_str(field(tokens)?)?;
+            let cost = E::from_str(field(tokens)?)?;
+            if lower > upper {
+                return Err(FlowError::MalformedFile(String::from("lower bound exceeds capacity")));
+            }
+            self.graph.add_edge_bounded(from, to, &lower, &upper, &cost)?;
+        }
+        else {
+            let cap = T::from_str(field(tokens)?)?;
+            self.graph.add_edge2(from, to, &cap, &E::default())?;
+        }
+        self.arcs += 1;
+        Ok(())
+    }
+}
+
+// 解析一行，state为None时表示还没有读到问题行
+fn parse_line<T, E, M>(line : &str, state : &mut Option<State<T, E, M>>) -> Result<(), FlowError>
+    where
+        T : StrIO + Clone + Default + Sub<Output = T> + PartialOrd,
+        E : StrIO + Clone + Default,
+        M : MulTE<T, E> {
+    let mut tokens = line.split_whitespace();
+    let kind = match tokens.next() {
+        None | Some("c") => return Ok(()),
+        Some(k) => k
+    };
+    if kind == "p" {
+        if state.is_some() {
+            return Err(FlowError::MalformedFile(String::from("duplicate problem line")));
+        }
+        let min = match field(&mut tokens)? {
+            "max" => false,
+            "min" => true,
+            p => return Err(FlowError::UnsupportedGraph(format!("problem type {}", p)))
+        };
+        let n = usize::from_str(field(&mut tokens)?)?;
+        let expected = usize::from_str(field(&mut tokens)?)?;
+        *state = Some(State { graph : Graph::new(), min, n, lines : 0, expected, arcs : 0, s : None, t : None });
+    }
+    else {
+        let st = state.as_mut().ok_or_else(|| FlowError::MalformedFile(String::from("missing problem line")))?;
+        match kind {
+            "n" => st.node_line(&mut tokens)?,
+            "a" => st.arc_line(&mut tokens)?,
+            _ => return Err(FlowError::MalformedFile(format!("unknown line type {}", kind)))
+        }
+    }
+    if tokens.next().is_some() {
+        return Err(FlowError::MalformedFile(String::from("too many fields")));
+    }
+    Ok(())
+}
+
+/// 从DIMACS格式的输入中读取一个图，返回图和问题的类型
+///
+/// 某一行的内容不合法时返回FlowError::Line以及行号和具体的错误，
+/// 缺少问题行、源点或汇点，边数与问题行不一致，或者问题行中的点数远大于
+/// 文件中用到的点数时返回FlowError::MalformedFile。
+/// 最小费用流问题中边的下界与add_edge_bounded的处理相同。
+///
+/// ```
+/// use network_flow::graph::Graph;
+/// use network_flow::io::dimacs::{read_dimacs, DimacsProblem};
+/// let s = "c example\np max 3 2\nn 1 s\nn 3 t\na 1 2 4\na 2 3 3\n";
+/// let (mut g, p) : (Graph<usize, u32, u32>, _) = read_dimacs(s.as_bytes()).unwrap();
+/// assert_eq!(p, DimacsProblem::MaxFlow { s : 0, t : 2 });
+/// assert_eq!(g.get_max_flow(0, 2), Ok(3));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn read_dimacs<T, E, M, R>(r : R) -> Result<(Graph<usize, T, E, M>, DimacsProblem), FlowError>
+    where
+        T : StrIO + Clone + Default + Sub<Output = T> + PartialOrd,
+        E : StrIO + Clone + Default,
+        M : MulTE<T, E>,
+        R : Read {
+    let mut state = None;
+    for (i, line) in BufReader::new(r).lines().enumerate() {
+        let line = line?;
+        parse_line(&line, &mut state).map_err(|e| FlowError::Line(i + 1, Box::new(e)))?;
+    }
+    let mut state = state.ok_or_else(|| FlowError::MalformedFile(String::from("missing problem line")))?;
+    state.grow(state.n)?;
+    if state.arcs != state.expected {
+        return Err(FlowError::MalformedFile(format!("expected {} arcs, found {}", state.expected, state.arcs)));
+    }
+    let problem = if state.min {
+        DimacsProblem::MinCostFlow
+    }
+    else {
+        match (state.s, state.t) {
+            (Some(s), Some(t)) => DimacsProblem::MaxFlow { s, t },
+            _ => return Err(FlowError::MalformedFile(String::from("missing source or sink")))
+        }
+    };
+    Ok((state.graph, problem))
+}
+
+fn check<L : Clone + Hash + Eq, T, E, M : MulTE<T, E>>(g : &Graph<L, T, E, M>, x : usize) -> Result<(), FlowError> {
+    if x < g.node_count() { Ok(()) } else { Err(FlowError::InvalidNode(x)) }
+}
+
+/// 将图以DIMACS最大流问题的格式写入w中，s和t为源点和汇点
+///
+/// 点的编号为图中的编号加1，每条边的容量为添加边时的容量上界
+pub fn write_max_flow<L, T, E, M, W>(g : &Graph<L, T, E, M>, s : usize, t : usize, mut w : W) -> Result<(), FlowError>
+    where
+        L : Clone + Hash + Eq,
+        T : StrIO + Clone + Default + Add<Output = T>,
+        E : Clone + Default,
+        M : MulTE<T, E>,
+        W : Write {
+    check(g, s)?;
+    check(g, t)?;
+    writeln!(w, "p max {} {}", g.node_count(), g.edges_with_flow().count())?;
+    writeln!(w, "n {} s", s + 1)?;
+    writeln!(w, "n {} t", t + 1)?;
+    for (id, from, to, _) in g.edges_with_flow() {
+        writeln!(w, "a {} {} {}", from + 1, to + 1, g.edge_capacity(id.0).to_str())?;
+    }
+    w.flush()?;
+    Ok(())
+}
+
+/// 将图以DIMACS最小费用流问题的格式写入w中
+///
+/// 每个点的供给为set_supply和set_demand设置的值之差，只输出不为0的点
+///
+/// ```
+/// use network_flow::graph::Graph;
+/// use network_flow::io::dimacs::write_min_cost_flow;
+/// let mut g = Graph::<usize, u32, i32>::create_graph(&[0, 1]);
+/// g.set_supply(0, &2).unwrap();
+/// g.set_demand(1, &2).unwrap();
+/// g.add_edge_bounded(0, 1, &1, &3, &-4).unwrap();
+/// let mut buf = vec![];
+/// write_min_cost_flow(&g, &mut buf).unwrap();
+/// assert_eq!(String::from_utf8(buf).unwrap(), "p min 2 1\nn 1 2\nn 2 -2\na 1 2 1 3 -4\n");
+/// ```
+pub fn write_min_cost_flow<L, T, E, M, W>(g : &Graph<L, T, E, M>, mut w : W) -> Result<(), FlowError>
+    where
+        L : Clone + Hash + Eq,
+        T : StrIO + Clone + Default + Add<Output = T> + Sub<Output = T> + PartialOrd,
+        E : StrIO + Clone + Default,
+        M : MulTE<T, E>,
+        W : Write {
+    writeln!(w, "p min {} {}", g.node_count(), g.edges_with_flow().count())?;
+    for i in 0..g.node_count() {
+        let (supply, demand) = (g.node_supply(i), g.node_demand(i));
+        if supply > demand {
+            writeln!(w, "n {} {}", i + 1, (supply - demand).to_str())?;
+        }
+        else if demand > supply {
+            writeln!(w, "n {} -{}", i + 1, (demand - supply).to_str())?;
+        }
+    }
+    for (id, from, to, _) in g.edges_with_flow() {
+        writeln!(w, "a {} {} {} {} {}", from + 1, to + 1,
+            g.edges[id.0].lower.to_str(), g.edge_capacity(id.0).to_str(), g.edges[id.0].cost.to_str())?;
+    }
+    w.flush()?;
+    Ok(())
+}
@@ -0,0 +1,203 @@
+//! GraphML格式的输入输出，需要开启`graphml` feature
+//!
+//! 输出时点的id为`n`加上编号，边的id为`e`加上添加的顺序。点有`label`、`supply`和`demand`属性，
+//! 边有`capacity`、`lower`、`cost`和`flow`属性，其中`label`的类型为string，其余属性的类型为double，
+//! 均通过StrIO转为字符串。
+//!
+//! 读入时通过`key`元素的`attr.name`确定属性的含义，`key`中的`default`作为缺省值。
+//! 点以`label`属性(缺省为id)作为标签，边必须有`capacity`属性，`lower`和`cost`缺省为0，`flow`缺省为下界。
+//! 无向的边被看作两条方向相反、容量和费用相同的边，流量属于从source指向target的边。
+
+use crate::costtype::MulTE;
+use crate::error::FlowError;
+use crate::graph::Graph;
+use super::{add_text_node, read_text, StrIO, TextEdge};
+use core::ops::Add;
+use core::ops::Sub;
+use roxmltree::{Document, Node};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::io::{Read, Write};
+
+const NODE_KEYS : [(&str, &str); 3] = [("label", "string"), ("supply", "double"), ("demand", "double")];
+const EDGE_KEYS : [(&str, &str); 4] = [("capacity", "double"), ("lower", "double"), ("cost", "double"), ("flow", "double")];
+
+fn escape(s : &str) -> String {
+    let mut res = String::new();
+    for c in s.chars() {
+        match c {
+            '&' => res.push_str("&amp;"),
+            '<' => res.push_str("&lt;"),
+            '>' => res.push_str("&gt;"),
+            '"' => res.push_str("&quot;"),
+            '\'' => res.push_str("&apos;"),
+            _ => res.push(c)
+        }
+    }
+    res
+}
+
+/// 将图以GraphML格式写入w中
+///
+/// ```
+/// use network_flow::graph::Graph;
+/// use network_flow::io::graphml::{read_graphml, write_graphml};
+/// let mut g = Graph::<String, u32, i32>::new();
+/// g.add_edge2_by_label(&"s".to_string(), &"t".to_string(), &3, &2);
+/// g.get_max_flow(0, 1).unwrap();
+/// let mut buf = vec![];
+/// write_graphml(&g, &mut buf).unwrap();
+/// let h : Graph<String, u32, i32> = read_graphml(&buf[..]).unwrap();
+/// assert_eq!(h.edges_with_flow().next().unwrap().3, 3);
+/// ```
+pub fn write_graphml<L, T, E, M, W>(g : &Graph<L, T, E, M>, mut w : W) -> Result<(), FlowError>
+    where
+        L : StrIO + Clone + Hash + Eq,
+        T : StrIO + Clone + Default + Add<Output = T> + PartialEq,
+        E : StrIO + Clone + Default,
+        M : MulTE<T, E>,
+        W : Write {
+    writeln!(w, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(w, "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">")?;
+    for (name, ty) in NODE_KEYS {
+        writeln!(w, "  <key id=\"{0}\" for=\"node\" attr.name=\"{0}\" attr.type=\"{1}\"/>", name, ty)?;
+    }
+    for (name, ty) in EDGE_KEYS {
+        writeln!(w, "  <key id=\"{0}\" for=\"edge\" attr.name=\"{0}\" attr.type=\"{1}\"/>", name, ty)?;
+    }
+    writeln!(w, "  <graph id=\"G\" edgedefault=\"directed\">")?;
+    for i in 0..g.node_count() {
+        write!(w, "    <node id=\"n{}\">", i)?;
+        if let Some(label) = g.get_label(i) {
+            write!(w, "<data key=\"label\">{}</data>", escape(&label.to_str()))?;
+        }
+        let (supply, demand) = (g.node_supply(i), g.node_demand(i));
+        if supply != T::default() {
+            write!(w, "<data key=\"supply\">{}</data>", escape(&supply.to_str()))?;
+        }
+        if demand != T::default() {
+            write!(w, "<data key=\"demand\">{}</data>", escape(&demand.to_str()))?;
+        }
+        writeln!(w, "</node>")?;
+    }
+    for (k, (id, from, to, flow)) in g.edges_with_flow().enumerate() {
+        write!(w, "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\">", k, from, to)?;
+        let values = [g.edge_capacity(id.0).to_str(), g.edges[id.0].lower.to_str(), g.edges[id.0].cost.to_str(), flow.to_str()];
+        for ((name, _), value) in EDGE_KEYS.iter().zip(values) {
+            write!(w, "<data key=\"{}\">{}</data>", name, escape(&value))?;
+        }
+        writeln!(w, "</edge>")?;
+    }
+    writeln!(w, "  </graph>")?;
+    writeln!(w, "</graphml>")?;
+    w.flush()?;
+    Ok(())
+}
+
+// GraphML中key元素的信息
+struct Key {
+    name : String,
+    domain : String,
+    default : Option<String>
+}
+
+fn elements<'a, 'input>(node : Node<'a, 'input>, name : &'static str) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(move |x| x.is_element() && x.tag_name().name() == name)
+}
+
+// 元素的所有属性，包括key中的缺省值
+fn data(node : Node, keys : &HashMap<String, Key>, domain : &str) -> HashMap<String, String> {
+    let mut res = HashMap::new();
+    for key in keys.values() {
+        if key.domain == domain || key.domain == "all" {
+            if let Some(x) = &key.default {
+                res.insert(key.name.clone(), x.clone());
+            }
+        }
+    }
+    for d in elements(node, "data") {
+        let id = d.attribute("key").unwrap_or_default();
+        let name = keys.get(id).map_or(id, |x| x.name.as_str());
+        res.insert(name.to_string(), d.text().unwrap_or_default().to_string());
+    }
+    res
+}
+
+fn attribute<'a>(node : Node<'a, '_>, name : &str) -> Result<&'a str, FlowError> {
+    node.attribute(name).ok_or_else(|| FlowError::MalformedFile(format!("{} without {}", node.tag_name().name(), name)))
+}
+
+/// 从r中读取GraphML格式的图
+///
+/// 文件中只能有一个graph元素。XML无法解析时返回FlowError::Parse，
+/// 含有嵌套的图、超边或者端口时返回FlowError::UnsupportedGraph，
+/// 缺少必要的属性、边的端点不存在或者标签重复时返回FlowError::MalformedFile。
+pub fn read_graphml<L, T, E, M, R>(r : R) -> Result<Graph<L, T, E, M>, FlowError>
+    where
+        L : StrIO + Clone + Hash + Eq,
+        T : StrIO + Clone + Default + Sub<Output = T> + PartialOrd,
+        E : StrIO + Clone + Default,
+        M : MulTE<T, E>,
+        R : Read {
+    let bad = |s : &str| FlowError::MalformedFile(String::from(s));
+    let unsupported = |s : &str| FlowError::UnsupportedGraph(String::from(s));
+    let text = read_text(r)?;
+    let doc = Document::parse(&text).map_err(|e| FlowError::Parse(e.to_string()))?;
+    let root = doc.root_element();
+    if root.tag_name().name() != "graphml" {
+        return Err(bad("missing graphml element"));
+    }
+    let mut keys = HashMap::new();
+    for k in elements(root, "key") {
+        let id = attribute(k, "id")?.to_string();
+        let name = k.attribute("attr.name").unwrap_or(&id).to_string();
+        let domain = k.attribute("for").unwrap_or("all").to_string();
+        let default = elements(k, "default").next().map(|x| x.text().unwrap_or_default().to_string());
+        keys.insert(id, Key { name, domain, default });
+    }
+    let mut graphs = elements(root, "graph");
+    let graph = graphs.next().ok_or_else(|| bad("missing graph element"))?;
+    if graphs.next().is_some() {
+        return Err(unsupported("multiple graphs"));
+    }
+    if elements(graph, "hyperedge").next().is_some() {
+        return Err(unsupported("hyperedge"));
+    }
+    let directed = graph.attribute("edgedefault") != Some("undirected");
+    let mut res = Graph::new();
+    let mut ids = HashMap::new();
+    for node in elements(graph, "node") {
+        if elements(node, "graph").next().is_some() {
+            return Err(unsupported("nested graph"));
+        }
+        if elements(node, "port").next().is_some() {
+            return Err(unsupported("node port"));
+        }
+        let id = attribute(node, "id")?;
+        let a = data(node, &keys, "node");
+        let label = a.get("label").map_or(id, |x| x.as_str());
+        let x = add_text_node(&mut res, label, a.get("supply").map(|x| x.as_str()), a.get("demand").map(|x| x.as_str()))?;
+        if ids.insert(id, x).is_some() {
+            return Err(bad("duplicate node id"));
+        }
+    }
+    for edge in elements(graph, "edge") {
+        if edge.attribute("sourceport").is_some() || edge.attribute("targetport").is_some() {
+            return Err(unsupported("node port"));
+        }
+        let node = |name : &str| -> Result<usize, FlowError> {
+            let id = attribute(edge, name)?;
+            ids.get(id).copied().ok_or_else(|| FlowError::MalformedFile(format!("unknown node {}", id)))
+        };
+        let (from, to) = (node("source")?, node("target")?);
+        let directed = match edge.attribute("directed") {
+            Some(x) => x == "true",
+            None => directed
+        };
+        let a = data(edge, &keys, "edge");
+        let get = |x : &str| a.get(x).map(|x| x.as_str());
+        let text = TextEdge { capacity : get("capacity"), lower : get("lower"), cost : get("cost"), flow : get("flow") };
+        text.add_to(&mut res, from, to, directed)?;
+    }
+    Ok(res)
+}
@@ -1,14 +1,22 @@
 //! 实现对图进行输入和输出的module
 //! 
 
+pub mod csv;
+pub mod dimacs;
+#[cfg(feature = "graphml")]
+pub mod graphml;
+#[cfg(feature = "json")]
+pub mod node_link;
+
 /// 将数据从原来的形式与字节形式之间进行转换
 /// 
-/// 对基础的数字类型、Vec、String进行了实现
+/// 对基础的数字类型、Vec、String进行了实现，其中usize、isize和长度都以8个字节存储
 /// 
 /// 对于其他自定义类型，需要手动实现该trait，如：
 /// 
 /// ```
 /// use network_flow::io::BitIO;
+/// use network_flow::error::FlowError;
 /// use std::mem::size_of;
 /// struct MyStruct {
 ///     a : String,
@@ -24,28 +32,41 @@
 ///         res.append(&mut self.c.to_bit());
 ///         res
 ///     }
-///     fn from_bit(a : &[u8]) -> Self {
-///         let mut temp = size_of::<usize>();
-///         let len = usize::from_bit(a);
-///         let temp1 = String::from_bit(&a[temp..]);
+///     fn from_bit(a : &[u8]) -> Result<Self, FlowError> {
+///         // usize和长度在字节形式中都固定占用8个字节
+///         let mut temp = size_of::<u64>();
+///         let len = usize::from_bit(a)?;
+///         let temp1 = String::from_bit(a.get(temp..).ok_or(FlowError::TruncatedInput)?)?;
 ///         temp = temp + len;
-///         let temp2 = u32::from_bit(&a[temp..]);
+///         let temp2 = u32::from_bit(a.get(temp..).ok_or(FlowError::TruncatedInput)?)?;
 ///         temp = temp + size_of::<u32>();
-///         let temp3 = Vec::<String>::from_bit(&a[temp..]);
-///         MyStruct { a: temp1, b: temp2, c: temp3 }
+///         let temp3 = Vec::<String>::from_bit(a.get(temp..).ok_or(FlowError::TruncatedInput)?)?;
+///         Ok(MyStruct { a: temp1, b: temp2, c: temp3 })
 ///     }
 /// }
 /// ```
 /// 
 /// 使用实现了该trait的类型构建的图，可以进行自动的文件输入输出，保存当前图中的状态或者读取原来的图的状态。
-pub trait BitIO {
+pub trait BitIO : Sized {
     /// 转为字节形式
     fn to_bit(&self) -> Vec<u8>;
-    /// 从字节形式生成
-    fn from_bit(a : &[u8]) -> Self;
+    /// 从字节形式生成，字节不足或者内容不合法时返回错误
+    fn from_bit(a : &[u8]) -> Result<Self, FlowError>;
 }
 
 use core::mem::size_of;
+use crate::error::FlowError;
+
+// 长度和usize在字节形式中固定占用8个字节
+const LEN_SIZE : usize = 8;
+
+// 从a的第pos个字节开始取出len个字节，并将pos移到这些字节之后
+fn take<'a>(a : &'a [u8], pos : &mut usize, len : usize) -> Result<&'a [u8], FlowError> {
+    let end = pos.checked_add(len).ok_or(FlowError::TruncatedInput)?;
+    let res = a.get(*pos..end).ok_or(FlowError::TruncatedInput)?;
+    *pos = end;
+    Ok(res)
+}
 
 macro_rules! BitIOPrim {
     ($ty : ty) => {
@@ -58,12 +79,10 @@ macro_rules! BitIOPrim {
                 }
                 res
             }
-            fn from_bit(a : &[u8]) -> Self {
+            fn from_bit(a : &[u8]) -> Result<Self, FlowError> {
                 let mut arr = [0; size_of::<$ty>()];
-                for i in 0..size_of::<$ty>() {
-                    arr[i] = a[i];
-                }
-                <$ty>::from_be_bytes(arr)
+                arr.copy_from_slice(take(a, &mut 0, size_of::<$ty>())?);
+                Ok(<$ty>::from_be_bytes(arr))
             }
         }
     };
@@ -74,83 +93,67 @@ BitIOPrim!(u16);
 BitIOPrim!(u32);
 BitIOPrim!(u64);
 BitIOPrim!(u128);
-BitIOPrim!(usize);
 BitIOPrim!(i8);
 BitIOPrim!(i16);
 BitIOPrim!(i32);
 BitIOPrim!(i64);
 BitIOPrim!(i128);
-BitIOPrim!(isize);
 BitIOPrim!(f32);
 BitIOPrim!(f64);
+
+// usize和isize按照u64和i64存储，使得字节形式在不同的平台之间通用
+impl BitIO for usize {
+    fn to_bit(&self) -> Vec<u8> {
+        (*self as u64).to_bit()
+    }
+    fn from_bit(a : &[u8]) -> Result<Self, FlowError> {
+        usize::try_from(u64::from_bit(a)?).map_err(|_| FlowError::Overflow)
+    }
+}
+impl BitIO for isize {
+    fn to_bit(&self) -> Vec<u8> {
+        (*self as i64).to_bit()
+    }
+    fn from_bit(a : &[u8]) -> Result<Self, FlowError> {
+        isize::try_from(i64::from_bit(a)?).map_err(|_| FlowError::Overflow)
+    }
+}
 impl<T : BitIO> BitIO for Vec<T> {
     fn to_bit(&self) -> Vec<u8> {
         let mut res = vec![];
-        let l = self.len().to_be_bytes();
-        for i in l {
-            res.push(i);
-        }
+        res.append(&mut self.len().to_bit());
         for i in self {
             let mut temp = i.to_bit();
-            let l = temp.len().to_be_bytes();
-            for i in l {
-                res.push(i);
-            }
+            res.append(&mut temp.len().to_bit());
             res.append(&mut temp);
         }
         res
     }
-    fn from_bit(a : &[u8]) -> Self {
+    fn from_bit(a : &[u8]) -> Result<Self, FlowError> {
         let mut res = vec![];
-        let mut buf = [0; size_of::<usize>()];
         let mut temp = 0;
-        for i in 0..size_of::<usize>() {
-            buf[i] = a[temp];
-            temp = temp + 1;
-        }
-        let len = usize::from_be_bytes(buf);
+        let len = usize::from_bit(take(a, &mut temp, LEN_SIZE)?)?;
         for _ in 0..len {
-            for i in 0..size_of::<usize>() {
-                buf[i] = a[temp];
-                temp = temp + 1;
-            }
-            let len = usize::from_be_bytes(buf);
-            let mut buf2 = vec![];
-            for _ in 0..len {
-                buf2.push(a[temp]);
-                temp = temp + 1;
-            }
-            res.push(T::from_bit(&buf2));
+            let len = usize::from_bit(take(a, &mut temp, LEN_SIZE)?)?;
+            res.push(T::from_bit(take(a, &mut temp, len)?)?);
         } 
-        res
+        Ok(res)
     }
 }
 impl BitIO for String {
     fn to_bit(&self) -> Vec<u8> {
         let mut res = vec![];
-        let l = self.as_bytes().len().to_be_bytes();
-        for i in l {
-            res.push(i);
-        }
+        res.append(&mut self.len().to_bit());
         for i in self.as_bytes() {
             res.push(*i);
         }
         res
     }
-    fn from_bit(a : &[u8]) -> Self {
-        let mut res = vec![];
-        let mut buf = [0; size_of::<usize>()];
+    fn from_bit(a : &[u8]) -> Result<Self, FlowError> {
         let mut temp = 0;
-        for i in 0..size_of::<usize>() {
-            buf[i] = a[temp];
-            temp = temp + 1;
-        }
-        let len = usize::from_be_bytes(buf);
-        for _ in 0..len {
-            res.push(a[temp]);
-            temp = temp + 1;
-        } 
-        String::from_utf8(res).expect("from_bit<String>:invalid utf8")
+        let len = usize::from_bit(take(a, &mut temp, LEN_SIZE)?)?;
+        let res = take(a, &mut temp, len)?.to_vec();
+        String::from_utf8(res).map_err(|_| FlowError::MalformedFile(String::from("invalid utf8 string")))
     }
 }
 
@@ -159,9 +162,10 @@ impl BitIO for String {
 /// 对基础的数字类型、String进行了实现
 /// 
 /// 对于其他自定义类型，需要手动实现该trait
-pub trait StrIO {
+pub trait StrIO : Sized {
     fn to_str(&self) -> String;
-    fn from_str(s : &str) -> Self;
+    /// 从字符串生成，无法解析时返回FlowError::Parse
+    fn from_str(s : &str) -> Result<Self, FlowError>;
 }
 
 macro_rules! StrIOPrim {
@@ -170,8 +174,8 @@ macro_rules! StrIOPrim {
             fn to_str(&self) -> String {
                 self.to_string()
             }
-            fn from_str(s : &str) -> Self {
-                s.parse::<$ty>().unwrap()
+            fn from_str(s : &str) -> Result<Self, FlowError> {
+                s.parse::<$ty>().map_err(|_| FlowError::Parse(String::from(s)))
             }
         }
     };
@@ -198,7 +202,74 @@ impl StrIO for String {
     fn to_str(&self) -> String {
         self.clone()
     }
-    fn from_str(s : &str) -> Self {
-        String::from(s)
+    fn from_str(s : &str) -> Result<Self, FlowError> {
+        Ok(String::from(s))
+    }
+}
+use crate::costtype::MulTE;
+use crate::graph::Graph;
+use crate::graph::edge::EdgeId;
+use core::ops::Sub;
+use std::hash::Hash;
+use std::io::Read;
+
+// 将r中的内容全部读取为字符串
+pub(crate) fn read_text<R : Read>(mut r : R) -> Result<String, FlowError> {
+    let mut buf = vec![];
+    r.read_to_end(&mut buf)?;
+    String::from_utf8(buf).map_err(|_| FlowError::MalformedFile(String::from("invalid utf8")))
+}
+
+// 由文本形式的标签、供给和需求添加一个点，标签重复时返回错误
+pub(crate) fn add_text_node<L, T, E, M>(g : &mut Graph<L, T, E, M>, label : &str, supply : Option<&str>, demand : Option<&str>)
+    -> Result<usize, FlowError>
+    where
+        L : StrIO + Clone + Hash + Eq,
+        T : StrIO + Clone + Default,
+        E : Clone + Default,
+        M : MulTE<T, E> {
+    let label = L::from_str(label)?;
+    if g.get_index(&label).is_some() {
+        return Err(FlowError::MalformedFile(String::from("duplicate node label")));
     }
-}
\ No newline at end of file
+    g.add_node(&label);
+    let x = g.node_count() - 1;
+    if let Some(supply) = supply {
+        g.set_supply(x, &T::from_str(supply)?)?;
+    }
+    if let Some(demand) = demand {
+        g.set_demand(x, &T::from_str(demand)?)?;
+    }
+    Ok(x)
+}
+
+// 文本格式中一条边的属性，下界和费用缺省为0，流量缺省为下界
+#[derive(Default)]
+pub(crate) struct TextEdge<'a> {
+    pub(crate) capacity : Option<&'a str>,
+    pub(crate) lower : Option<&'a str>,
+    pub(crate) cost : Option<&'a str>,
+    pub(crate) flow : Option<&'a str>
+}
+
+impl TextEdge<'_> {
+    // 添加到图中，无向边会再添加一条没有流量的反方向的边
+    pub(crate) fn add_to<L, T, E, M>(&self, g : &mut Graph<L, T, E, M>, from : usize, to : usize, directed : bool)
+        -> Result<EdgeId, FlowError>
+        where
+            L : Clone + Hash + Eq,
+            T : StrIO + Clone + Default + Sub<Output = T> + PartialOrd,
+            E : StrIO + Clone + Default,
+            M : MulTE<T, E> {
+        let capacity = self.capacity.ok_or_else(|| FlowError::MalformedFile(String::from("edge without capacity")))?;
+        let capacity = T::from_str(capacity)?;
+        let lower = self.lower.map(T::from_str).transpose()?.unwrap_or_default();
+        let cost = self.cost.map(E::from_str).transpose()?.unwrap_or_default();
+        let flow = self.flow.map(T::from_str).transpose()?.unwrap_or_else(|| lower.clone());
+        let id = g.add_edge_with_flow(from, to, &lower, &capacity, &cost, &flow)?;
+        if !directed {
+            g.add_edge_bounded(to, from, &lower, &capacity, &cost)?;
+        }
+        Ok(id)
+    }
+}
@@ -0,0 +1,156 @@
+//! node-link形式的JSON的输入输出，与networkx、d3等使用的格式相同，需要开启`json` feature
+//!
+//! 输出的对象包含`directed`、`multigraph`、`graph`、`nodes`和`links`。每个点的`id`为其编号，
+//! 有`label`属性，供给和需求不为0时有`supply`和`demand`属性；每条边有`source`、`target`、
+//! `capacity`、`lower`、`cost`和`flow`属性。StrIO得到的字符串作为JSON中的数字重新输出后不变时输出为数字，否则输出为字符串。
+//!
+//! 读入时点通过`id`查找，以`label`(缺省为id)作为标签，边的列表可以是`links`或者`edges`，
+//! 边必须有`capacity`属性，`lower`和`cost`缺省为0，`flow`缺省为下界，其他属性会被忽略。
+//! `directed`缺省为true，无向图中的每条边被看作两条方向相反、容量和费用相同的边。
+
+use crate::costtype::MulTE;
+use crate::error::FlowError;
+use crate::graph::Graph;
+use super::{add_text_node, StrIO, TextEdge};
+use core::ops::Add;
+use core::ops::Sub;
+use serde_json::{json, Map, Number, Value};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::io::{Read, Write};
+
+// 只有重新输出后文本不变的数字才写成JSON中的数字，例如"1e5"和"1.50"仍然写成字符串，读入时才能得到原来的文本
+fn value(s : String) -> Value {
+    match s.parse::<Number>() {
+        Ok(x) if x.to_string() == s => Value::Number(x),
+        _ => Value::String(s)
+    }
+}
+
+// JSON中的值的文本形式，字符串不含引号，null视为不存在
+fn text(v : Option<&Value>) -> Option<String> {
+    match v? {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        x => Some(x.to_string())
+    }
+}
+
+fn json_error(e : serde_json::Error) -> FlowError {
+    if e.is_io() {
+        FlowError::Io(e.into())
+    }
+    else if e.is_eof() {
+        FlowError::TruncatedInput
+    }
+    else {
+        FlowError::Parse(e.to_string())
+    }
+}
+
+/// 将图以node-link形式的JSON写入w中
+///
+/// ```
+/// use network_flow::graph::Graph;
+/// use network_flow::io::node_link::{read_node_link, write_node_link};
+/// let mut g = Graph::<String, u32, i32>::new();
+/// g.add_edge2_by_label(&"s".to_string(), &"t".to_string(), &3, &2);
+/// g.get_max_flow(0, 1).unwrap();
+/// let mut buf = vec![];
+/// write_node_link(&g, &mut buf).unwrap();
+/// let s = String::from_utf8(buf).unwrap();
+/// assert!(s.contains("\"capacity\":3"));
+/// let h : Graph<String, u32, i32> = read_node_link(s.as_bytes()).unwrap();
+/// assert_eq!(h.edges_with_flow().next().unwrap().3, 3);
+/// ```
+pub fn write_node_link<L, T, E, M, W>(g : &Graph<L, T, E, M>, mut w : W) -> Result<(), FlowError>
+    where
+        L : StrIO + Clone + Hash + Eq,
+        T : StrIO + Clone + Default + Add<Output = T> + PartialEq,
+        E : StrIO + Clone + Default,
+        M : MulTE<T, E>,
+        W : Write {
+    let mut nodes = vec![];
+    for i in 0..g.node_count() {
+        let mut node = Map::new();
+        node.insert(String::from("id"), json!(i));
+        if let Some(label) = g.get_label(i) {
+            node.insert(String::from("label"), value(label.to_str()));
+        }
+        let (supply, demand) = (g.node_supply(i), g.node_demand(i));
+        if supply != T::default() {
+            node.insert(String::from("supply"), value(supply.to_str()));
+        }
+        if demand != T::default() {
+            node.insert(String::from("demand"), value(demand.to_str()));
+        }
+        nodes.push(Value::Object(node));
+    }
+    let links : Vec<Value> = g.edges_with_flow().map(|(id, from, to, flow)| json!({
+        "source" : from,
+        "target" : to,
+        "capacity" : value(g.edge_capacity(id.0).to_str()),
+        "lower" : value(g.edges[id.0].lower.to_str()),
+        "cost" : value(g.edges[id.0].cost.to_str()),
+        "flow" : value(flow.to_str())
+    })).collect();
+    let res = json!({
+        "directed" : true,
+        "multigraph" : true,
+        "graph" : {},
+        "nodes" : nodes,
+        "links" : links
+    });
+    serde_json::to_writer(&mut w, &res).map_err(json_error)?;
+    w.flush()?;
+    Ok(())
+}
+
+/// 从r中读取node-link形式的JSON
+///
+/// JSON无法解析时返回FlowError::Parse，缺少点或边的列表、缺少必要的属性、
+/// 点的id重复或者边的端点不存在时返回FlowError::MalformedFile。
+pub fn read_node_link<L, T, E, M, R>(r : R) -> Result<Graph<L, T, E, M>, FlowError>
+    where
+        L : StrIO + Clone + Hash + Eq,
+        T : StrIO + Clone + Default + Sub<Output = T> + PartialOrd,
+        E : StrIO + Clone + Default,
+        M : MulTE<T, E>,
+        R : Read {
+    let bad = |s : &str| FlowError::MalformedFile(String::from(s));
+    let v : Value = serde_json::from_reader(r).map_err(json_error)?;
+    let directed = match v.get("directed") {
+        None => true,
+        Some(x) => x.as_bool().ok_or_else(|| bad("directed is not a boolean"))?
+    };
+    let nodes = v.get("nodes").and_then(Value::as_array).ok_or_else(|| bad("missing nodes"))?;
+    let links = v.get("links").or_else(|| v.get("edges")).and_then(Value::as_array).ok_or_else(|| bad("missing links"))?;
+    let mut res = Graph::new();
+    let mut ids = HashMap::new();
+    for node in nodes {
+        let id = text(node.get("id")).ok_or_else(|| bad("node without id"))?;
+        let label = text(node.get("label")).unwrap_or_else(|| id.clone());
+        let (supply, demand) = (text(node.get("supply")), text(node.get("demand")));
+        let x = add_text_node(&mut res, &label, supply.as_deref(), demand.as_deref())?;
+        if ids.insert(id, x).is_some() {
+            return Err(bad("duplicate node id"));
+        }
+    }
+    for link in links {
+        let node = |name : &str| -> Result<usize, FlowError> {
+            let id = text(link.get(name)).ok_or_else(|| FlowError::MalformedFile(format!("link without {}", name)))?;
+            ids.get(&id).copied().ok_or_else(|| FlowError::MalformedFile(format!("unknown node {}", id)))
+        };
+        let (from, to) = (node("source")?, node("target")?);
+        let get = |x : &str| text(link.get(x));
+        let (capacity, lower, cost, flow) = (get("capacity"), get("lower"), get("cost"), get("flow"));
+        let edge = TextEdge {
+            capacity : capacity.as_deref(),
+            lower : lower.as_deref(),
+            cost : cost.as_deref(),
+            flow : flow.as_deref()
+        };
+        edge.add_to(&mut res, from, to, directed)?;
+    }
+    Ok(res)
+}
@@ -3,7 +3,14 @@
 //!
 //! 其中，图中的容量和费用可以是任意的基础数字类型或者自定义的类型。
 //! 
+//! 开启`serde` feature后，图以及边的编号、流分解等结果类型可以使用serde进行序列化和反序列化，
+//! 图会被表示为点和边的列表。
+//! 
+//! GraphML和node-link JSON格式的读写分别需要开启`graphml`和`json` feature，
+//! 其他格式不需要额外的依赖。
+//! 
 
 pub mod graph;
 pub mod costtype;
-pub mod io;
\ No newline at end of file
+pub mod io;
+pub mod error;
\ No newline at end of file
@@ -0,0 +1,31 @@
+//! 测试中共用的随机图
+#![allow(dead_code)]
+
+use network_flow::graph::Graph;
+
+/// 线性同余生成器
+pub struct Lcg(pub u64);
+
+impl Lcg {
+    pub fn next(&mut self) -> usize {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.0 >> 33) as usize
+    }
+}
+
+/// m条随机的边(起点, 终点, 容量, 费用)，容量小于10，费用小于20，可能有自环
+pub fn random_edges(n : usize, m : usize, seed : u64) -> Vec<(usize, usize, i64, i64)> {
+    let mut rng = Lcg(seed);
+    (0..m).map(|_| (rng.next() % n, rng.next() % n, (rng.next() % 10) as i64, (rng.next() % 20) as i64)).collect()
+}
+
+/// 由random_edges中不是自环的边组成的图
+pub fn random_graph(n : usize, m : usize, seed : u64) -> Graph<usize, i64, i64> {
+    let mut g = Graph::<usize, i64, i64>::create_graph(&(0..n).collect::<Vec<_>>());
+    for (u, v, w, c) in random_edges(n, m, seed) {
+        if u != v {
+            g.add_edge2(u, v, &w, &c).unwrap();
+        }
+    }
+    g
+}
@@ -0,0 +1,88 @@
+use network_flow::error::FlowError;
+use network_flow::graph::Graph;
+use network_flow::io::csv::{read_csv, write_csv, CsvColumn, CsvFormat};
+
+#[test]
+fn csv_round_trip() {
+    let mut g = Graph::<String, i64, i64>::new();
+    let labels = ["s", "a, \"b\"", " c ", "t"].map(String::from);
+    for (i, (u, v)) in [(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)].into_iter().enumerate() {
+        g.add_edge2_by_label(&labels[u], &labels[v], &(i as i64 + 2), &(i as i64 - 1));
+    }
+    g.add_edge_bounded(2, 1, &1, &3, &2).unwrap();
+    g.mcmf(0, 3).unwrap();
+    for format in [CsvFormat::default(), CsvFormat { delimiter : ';', ..CsvFormat::default() }] {
+        let mut buf = vec![];
+        write_csv(&g, &mut buf, &format).unwrap();
+        let mut h = Graph::<String, i64, i64>::new();
+        read_csv(&mut h, &buf[..], &format).unwrap();
+        for (i, label) in labels.iter().enumerate() {
+            assert_eq!(h.get_index(label), Some(i));
+        }
+        assert_eq!(h.edges_with_flow().collect::<Vec<_>>(), g.edges_with_flow().collect::<Vec<_>>());
+        for (id, ..) in g.edges_with_flow() {
+            assert_eq!((h.get_capacity(id), h.get_cost(id), h.get_lower(id)), (g.get_capacity(id), g.get_cost(id), g.get_lower(id)));
+        }
+    }
+    // 每行为一条边，包含换行的标签无法读回
+    g.add_node(&String::from("x\ny"));
+    g.add_edge(4, 3, &1).unwrap();
+    assert_eq!(write_csv(&g, vec![], &CsvFormat::default()), Err(FlowError::MalformedFile(String::from("line break in field"))));
+}
+
+#[test]
+fn csv_columns() {
+    // 已有的标签对应原有的点
+    let mut g = Graph::<usize, u32, i32>::create_graph(&[7, 3]);
+    let s = "3\t9\t4\n\n7\t3\t5\n7\t9\t1\n";
+    let format = CsvFormat {
+        delimiter : '\t',
+        from : CsvColumn::Index(0),
+        to : CsvColumn::Index(1),
+        capacity : CsvColumn::Index(2),
+        ..CsvFormat::without_header()
+    };
+    let edges = read_csv(&mut g, s.as_bytes(), &format).unwrap();
+    assert_eq!(edges.len(), 3);
+    assert_eq!(g.get_index(&9), Some(2));
+    assert_eq!(g.get_max_flow(0, 2), Ok(5));
+    // 表头中没有的可选列被忽略，空的字段取缺省值
+    let mut g = Graph::<usize, u32, i32>::new();
+    read_csv(&mut g, "capacity,to,from,flow\n3,2,1,\n4,3,1,2\n".as_bytes(), &CsvFormat::default()).unwrap();
+    assert_eq!(g.edges_with_flow().map(|x| x.3).collect::<Vec<_>>(), vec![0, 2]);
+}
+
+#[test]
+fn csv_errors() {
+    let read = |s : &str, format : &CsvFormat| {
+        let mut g = Graph::<usize, u32, i32>::new();
+        read_csv(&mut g, s.as_bytes(), format).err()
+    };
+    let line = |i, s : &str| Some(FlowError::Line(i, Box::new(FlowError::MalformedFile(String::from(s)))));
+    let default = CsvFormat::default();
+    assert_eq!(read("from,to,cost\n1,2,3\n", &default), line(1, "missing column capacity"));
+    assert_eq!(read("from,to,capacity\n1,2,3\n\n1,2\n", &default), line(4, "missing field 2"));
+    assert_eq!(read("from,to,capacity\n1,2,3\n1,\"2,3\n", &default), line(3, "unterminated quote"));
+    assert_eq!(read("from,to,capacity,lower\n1,2,3,4\n", &default), Some(FlowError::Line(2, Box::new(FlowError::Infeasible(vec![0, 1])))));
+    assert_eq!(read("from,to,capacity,flow\n1,2,3,4\n", &default), line(2, "flow must be between the lower bound and the capacity"));
+    assert!(matches!(read("from,to,capacity\n1,x,3\n", &default), Some(FlowError::Line(2, _))));
+    let format = CsvFormat { has_header : false, ..CsvFormat::default() };
+    assert_eq!(read("1,2,3\n", &format), Some(FlowError::MalformedFile(String::from("column name without header"))));
+}
+
+#[test]
+fn csv_error_keeps_earlier_rows() {
+    // 出错的行中新添加的点被删除，之前的行仍然保留
+    let mut g = Graph::<usize, u32, i32>::new();
+    let s = "from,to,capacity,lower\n1,2,3,\n2,5,4,\n6,7,3,4\n";
+    assert_eq!(read_csv(&mut g, s.as_bytes(), &CsvFormat::default()),
+        Err(FlowError::Line(4, Box::new(FlowError::Infeasible(vec![3, 4])))));
+    assert_eq!(g.node_count(), 3);
+    assert_eq!((g.get_index(&6), g.get_index(&7)), (None, None));
+    assert_eq!(g.edges_with_flow().count(), 2);
+    assert_eq!(read_csv(&mut g, "from,to,capacity\n6,1,x\n".as_bytes(), &CsvFormat::default()).map(|_| ()),
+        Err(FlowError::Line(2, Box::new(FlowError::Parse(String::from("x"))))));
+    assert_eq!(g.node_count(), 3);
+    g.add_node(&6);
+    assert_eq!(g.get_index(&6), Some(3));
+}
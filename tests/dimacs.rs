@@ -0,0 +1,64 @@
+use network_flow::costtype::MulTEDefaultType;
+use network_flow::error::FlowError;
+use network_flow::graph::Graph;
+use network_flow::io::dimacs::{read_dimacs, write_max_flow, write_min_cost_flow, DimacsProblem};
+
+mod common;
+use common::random_graph;
+
+#[test]
+fn max_flow_round_trip() {
+    for seed in 0..10 {
+        let mut g = random_graph(20, 80, seed);
+        let mut buf = vec![];
+        write_max_flow(&g, 0, 19, &mut buf).unwrap();
+        let (mut h, p) : (Graph<usize, i64, i64>, _) = read_dimacs(&buf[..]).unwrap();
+        assert_eq!(p, DimacsProblem::MaxFlow { s : 0, t : 19 });
+        assert_eq!(h.get_label(0), Some(&1));
+        assert_eq!(g.get_max_flow(0, 19), h.get_max_flow(0, 19));
+    }
+}
+
+#[test]
+fn min_cost_flow_round_trip() {
+    for seed in 0..10 {
+        let mut g = random_graph(15, 60, seed);
+        g.set_supply(0, &5).unwrap();
+        g.set_demand(14, &5).unwrap();
+        let mut buf = vec![];
+        write_min_cost_flow(&g, &mut buf).unwrap();
+        let (mut h, p) : (Graph<usize, i64, i64>, _) = read_dimacs(&buf[..]).unwrap();
+        assert_eq!(p, DimacsProblem::MinCostFlow);
+        assert_eq!((h.get_supply(0), h.get_demand(14)), (Ok(5), Ok(5)));
+        assert_eq!(g.min_cost_flow(), h.min_cost_flow());
+    }
+}
+
+#[test]
+fn line_errors() {
+    let read = |s : &str| read_dimacs::<u32, i32, MulTEDefaultType, _>(s.as_bytes()).map(|_| ()).err();
+    assert_eq!(read("p max 2 1\nn 1 s\nn 2 t\na 1 3 5\n"),
+        Some(FlowError::Line(4, Box::new(FlowError::InvalidNode(3)))));
+    assert_eq!(read("c comment\n\np min 2 1\nn 1 x\n"),
+        Some(FlowError::Line(4, Box::new(FlowError::Parse(String::from("x"))))));
+    assert_eq!(read("a 1 2 3\n"),
+        Some(FlowError::Line(1, Box::new(FlowError::MalformedFile(String::from("missing problem line"))))));
+    assert_eq!(read("p min 2 1\na 1 2 3 1 0\n"),
+        Some(FlowError::Line(2, Box::new(FlowError::MalformedFile(String::from("lower bound exceeds capacity"))))));
+    assert_eq!(read("p max 2 2\nn 1 s\nn 2 t\na 1 2 3\n"),
+        Some(FlowError::MalformedFile(String::from("expected 2 arcs, found 1"))));
+    assert_eq!(read("p max 2 0\nn 1 s\n"),
+        Some(FlowError::MalformedFile(String::from("missing source or sink"))));
+    assert!(matches!(read("p sp 2 0\n"), Some(FlowError::Line(1, e)) if matches!(*e, FlowError::UnsupportedGraph(_))));
+    assert_eq!(read("p max 10000000000000 0\n"),
+        Some(FlowError::MalformedFile(String::from("too many nodes: 10000000000000"))));
+    assert_eq!(read("p max 10000000000000 1\nn 1 s\nn 9999999999999 t\n"),
+        Some(FlowError::Line(3, Box::new(FlowError::MalformedFile(String::from("too many nodes: 9999999999999"))))));
+}
+
+#[test]
+fn unused_nodes() {
+    let (g, _) : (Graph<usize, u32, u32>, _) = read_dimacs("p max 5 1\nn 1 s\nn 2 t\na 1 2 3\n".as_bytes()).unwrap();
+    assert_eq!(g.node_count(), 5);
+    assert_eq!(g.get_label(4), Some(&5));
+}
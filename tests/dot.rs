@@ -0,0 +1,77 @@
+use network_flow::graph::dot::DotOptions;
+use network_flow::graph::Graph;
+
+#[test]
+fn dot_round_trip() {
+    let mut g = Graph::<String, i32, i32>::new();
+    let s = String::from("source \"s\"");
+    let t = String::from("it's t\\");
+    g.set_supply_by_label(&s, &3).unwrap();
+    let a = g.add_edge2_by_label(&s, &String::from("a"), &3, &1);
+    g.add_edge2_by_label(&String::from("a"), &t, &2, &-2);
+    g.add_edge2_by_label(&s, &t, &2, &4);
+    g.add_edge_bounded(1, 2, &1, &2, &3).unwrap();
+    g.mcmf(0, 2).unwrap();
+    let mut buf = vec![];
+    g.write_dot(&mut buf).unwrap();
+    // 只有双引号和反斜杠需要转义
+    assert!(String::from_utf8(buf.clone()).unwrap().contains("\"it's t\\\\\""));
+    let h = Graph::<String, i32, i32>::read_dot(&buf[..]).unwrap();
+    assert_eq!(h.get_index(&t), Some(2));
+    assert_eq!(h.get_supply(0), Ok(3));
+    assert_eq!(h.edges_with_flow().collect::<Vec<_>>(), g.edges_with_flow().collect::<Vec<_>>());
+    for (id, ..) in g.edges_with_flow() {
+        assert_eq!((h.get_capacity(id), h.get_cost(id), h.get_lower(id)), (g.get_capacity(id), g.get_cost(id), g.get_lower(id)));
+    }
+    assert_eq!(h.get_flow(a), Ok(3));
+}
+
+#[test]
+fn dot_attributes() {
+    // 属性顺序任意，点的id不必是编号，旧版本的label也可以读取
+    let s = "digraph { t [label=z]; s -> t [flow=1, capacity=2, color=red]; s -> m -> t [label=\"0/5,2\"] }";
+    let mut g = Graph::<String, u32, i32>::read_dot(s.as_bytes()).unwrap();
+    assert_eq!(g.get_index(&String::from("z")), Some(0));
+    assert_eq!(g.get_index(&String::from("m")), Some(2));
+    assert_eq!(g.edges_with_flow().map(|x| x.3).collect::<Vec<_>>(), vec![1, 0, 0]);
+    assert_eq!(g.mcmf(1, 0), Ok((6, 20)));
+}
+
+#[test]
+fn dot_cut_style_round_trip() {
+    let mut g = Graph::<usize, u32, i32>::create_graph(&[0, 1, 2, 3]);
+    g.add_edge(0, 1, &3).unwrap();
+    g.add_edge(0, 2, &1).unwrap();
+    g.add_edge(1, 3, &1).unwrap();
+    g.add_edge(2, 3, &4).unwrap();
+    g.add_edge(3, 1, &2).unwrap();
+    assert_eq!(g.get_max_flow(0, 3), Ok(2));
+    let options = DotOptions { cut_source : Some(0), highlight_saturated : false, hide_zero_flow : true };
+    let mut buf = vec![];
+    g.write_dot_with(&mut buf, &options).unwrap();
+    let text = String::from_utf8(buf.clone()).unwrap();
+    // 割边为0->2和1->3
+    assert_eq!(text.matches("style=bold").count(), 2);
+    // 子图和隐藏的边不影响读入点的顺序
+    let h = Graph::<usize, u32, i32>::read_dot(&buf[..]).unwrap();
+    assert_eq!(h.get_cut(0), g.get_cut(0));
+    assert_eq!(h.edges_with_flow().count(), 4);
+    assert_eq!(g.write_dot_with(vec![], &DotOptions { cut_source : Some(4), ..options }).err(),
+        Some(network_flow::error::FlowError::InvalidNode(4)));
+}
+
+#[test]
+fn dot_strict_graph() {
+    let s = "strict digraph { s -> t [capacity=1]; s -> t [capacity=2] }";
+    assert_eq!(Graph::<String, u32, i32>::read_dot(s.as_bytes()).err(),
+        Some(network_flow::error::FlowError::UnsupportedGraph(String::from("strict graph"))));
+}
+
+#[test]
+fn dot_apostrophes() {
+    // 字符串中的单引号可以不转义，注释中的引号不影响读入
+    let s = "digraph { /* \"it's */ a [label=\"it's\"]; # '\"\n a -> \"b'\\\"\" [capacity=1] // \"\n}";
+    let g = Graph::<String, u32, i32>::read_dot(s.as_bytes()).unwrap();
+    assert_eq!(g.get_index(&String::from("it's")), Some(0));
+    assert_eq!(g.get_index(&String::from("b'\"")), Some(1));
+}
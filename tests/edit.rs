@@ -0,0 +1,189 @@
+use network_flow::error::FlowError;
+use network_flow::graph::Graph;
+
+mod common;
+use common::Lcg;
+
+fn build(n : usize, edges : &[(usize, usize, i64, i64)]) -> Graph<usize, i64, i64> {
+    let mut g = Graph::create_graph(&(0..n).collect::<Vec<_>>());
+    for &(u, v, w, c) in edges {
+        g.add_edge2(u, v, &w, &c).unwrap();
+    }
+    g
+}
+
+fn sorted_edges(g : &Graph<usize, i64, i64>) -> Vec<(usize, usize, i64, i64)> {
+    let mut res : Vec<_> = g.edges_with_flow().map(|(id, u, v, _)| (u, v, g.get_capacity(id).unwrap(), g.get_cost(id).unwrap())).collect();
+    res.sort();
+    res
+}
+
+#[test]
+fn remove_same_as_rebuild() {
+    let mut rng = Lcg(7);
+    for n in [3, 6, 15] {
+        let mut edges = vec![];
+        for _ in 0..n * 4 {
+            edges.push((rng.next() % n, rng.next() % n, (rng.next() % 10) as i64, (rng.next() % 7) as i64));
+        }
+        let mut g = build(n, &edges);
+        g.get_max_flow(0, n - 1).unwrap();
+        // 删除边之后其他边的编号不变，被删除的编号不再有效
+        let mut ids : Vec<_> = g.edges_with_flow().map(|x| x.0).collect();
+        for _ in 0..n {
+            let k = rng.next() % edges.len();
+            g.remove_edge(ids[k]).unwrap();
+            assert_eq!(g.get_flow(ids[k]), Err(FlowError::InvalidEdge(ids[k].index())));
+            edges.remove(k);
+            ids.remove(k);
+            let rest : Vec<_> = g.edges_with_flow().map(|(id, u, v, _)| (u, v, g.get_capacity(id).unwrap(), g.get_cost(id).unwrap())).collect();
+            assert_eq!(rest, edges);
+            assert_eq!(g.edges_with_flow().map(|x| x.0).collect::<Vec<_>>(), ids);
+        }
+        // 删除点时最后一个点移动到被删除的位置
+        let k = rng.next() % (n - 2) + 1;
+        assert_eq!(g.remove_node(k), Ok((k, Some(n - 1))));
+        assert_eq!(g.get_index(&k), None);
+        assert_eq!(g.get_index(&(n - 1)), Some(k));
+        let rename = |x : usize| if x == n - 1 { k } else { x };
+        edges.retain(|&(u, v, ..)| u != k && v != k);
+        let mut edges : Vec<_> = edges.into_iter().map(|(u, v, w, c)| (rename(u), rename(v), w, c)).collect();
+        edges.sort();
+        assert_eq!(sorted_edges(&g), edges);
+        let mut h = build(n - 1, &edges);
+        let mut g = build(n - 1, &sorted_edges(&g));
+        assert_eq!(g.get_max_flow(0, k), h.get_max_flow(0, k));
+        assert_eq!(g.mcmf(k, 0), h.mcmf(k, 0));
+    }
+}
+
+#[test]
+fn remove_keeps_residual_lists() {
+    // 删除之后在原图上继续求解，链表和反向边必须仍然正确
+    let mut g = Graph::<&str, i32, i32>::new();
+    g.add_edge2_by_label(&"s", &"a", &4, &1);
+    let loop_edge = g.add_edge2_by_label(&"a", &"a", &5, &1);
+    g.add_edge2_by_label(&"a", &"t", &3, &1);
+    g.add_edge2_by_label(&"s", &"b", &2, &3);
+    g.add_edge2_by_label(&"b", &"t", &2, &3);
+    g.add_edge2_by_label(&"a", &"b", &1, &1);
+    g.remove_edge(loop_edge).unwrap();
+    assert_eq!(g.get_endpoints(loop_edge), Err(FlowError::InvalidEdge(loop_edge.index())));
+    assert_eq!(g.mcmf_by_label(&"s", &"t"), Ok((5, 17)));
+    g.set_supply_by_label(&"b", &2).unwrap();
+    assert_eq!(g.remove_node(0), Ok(("s", Some(3))));
+    assert_eq!(g.get_supply(g.get_index(&"b").unwrap()), Ok(2));
+    assert_eq!(g.get_index(&"b"), Some(0));
+    assert_eq!(g.get_neighbor(0).unwrap().len(), 2);
+}
+
+#[test]
+fn set_capacity_warm_same_as_rebuild() {
+    let mut rng = Lcg(11);
+    for n in [4, 10, 30] {
+        let mut edges = vec![];
+        for _ in 0..n * 5 {
+            edges.push((rng.next() % n, rng.next() % n, (rng.next() % 10) as i64, 0));
+        }
+        let mut g = build(n, &edges);
+        let ids : Vec<_> = g.edges_with_flow().map(|x| x.0).collect();
+        g.get_max_flow(0, n - 1).unwrap();
+        for _ in 0..n * 3 {
+            let k = rng.next() % edges.len();
+            edges[k].2 = (rng.next() % 10) as i64;
+            g.set_capacity(ids[k], &edges[k].2).unwrap();
+            // 修复之后的流仍然合法
+            let mut balance = vec![0; n];
+            for (id, u, v, f) in g.edges_with_flow() {
+                assert!(0 <= f && f <= g.get_capacity(id).unwrap());
+                balance[u] += f;
+                balance[v] -= f;
+            }
+            assert!(balance[1..n - 1].iter().all(|&b| b == 0));
+            assert_eq!(g.max_flow_warm(0, n - 1), build(n, &edges).get_max_flow(0, n - 1));
+        }
+    }
+}
+
+#[test]
+fn set_capacity_and_cost_errors() {
+    let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2]);
+    let a = g.add_edge_bounded(0, 1, &1, &3, &2).unwrap();
+    assert_eq!(g.set_capacity(a, &0), Err(FlowError::Infeasible(vec![0, 1])));
+    assert_eq!(g.get_capacity(a), Ok(3));
+    g.remove_edge(a).unwrap();
+    assert_eq!(g.set_capacity(a, &1), Err(FlowError::InvalidEdge(0)));
+    // 0是源点，多出的流量沿1到2的边退回
+    let a = g.add_edge2(0, 1, &3, &1).unwrap();
+    let b = g.add_edge2(1, 2, &2, &1).unwrap();
+    assert_eq!(g.max_flow_warm(0, 2), Ok(2));
+    g.set_capacity(a, &1).unwrap();
+    assert_eq!((g.get_flow(a), g.get_flow(b)), (Ok(1), Ok(1)));
+    g.set_cost(b, &5).unwrap();
+    assert_eq!(g.get_cost(b), Ok(5));
+    g.remove_edge(b).unwrap();
+    assert_eq!(g.set_cost(b, &1), Err(FlowError::InvalidEdge(b.index())));
+}
+
+#[test]
+fn reset_flow_same_as_rebuild() {
+    let mut rng = Lcg(5);
+    let n = 12;
+    let edges : Vec<_> = (0..50).map(|_| (rng.next() % n, rng.next() % n, (rng.next() % 10) as i64, (rng.next() % 7) as i64)).collect();
+    let mut g = build(n, &edges);
+    g.get_max_flow(0, n - 1).unwrap();
+    for (s, t) in [(1, 2), (n - 1, 0), (3, 4)] {
+        g.reset_flow();
+        assert!(g.edges_with_flow().all(|x| x.3 == 0));
+        assert_eq!(g.mcmf(s, t), build(n, &edges).mcmf(s, t));
+    }
+}
+
+#[test]
+fn snapshot_and_clone() {
+    let mut rng = Lcg(3);
+    let n = 10;
+    let edges : Vec<_> = (0..40).map(|_| (rng.next() % n, rng.next() % n, (rng.next() % 10) as i64, (rng.next() % 7) as i64)).collect();
+    let mut g = build(n, &edges);
+    let ids : Vec<_> = g.edges_with_flow().map(|x| x.0).collect();
+    let total = g.max_flow_warm(0, n - 1).unwrap();
+    let saved = g.snapshot();
+    let copy = g.clone();
+    // 依次尝试去掉每条边，之后回滚
+    for (k, &id) in ids.iter().enumerate() {
+        g.set_capacity(id, &0).unwrap();
+        g.set_cost(id, &100).unwrap();
+        let mut rest = edges.clone();
+        rest[k].2 = 0;
+        assert_eq!(g.max_flow_warm(0, n - 1), build(n, &rest).get_max_flow(0, n - 1));
+        g.restore(&saved).unwrap();
+        assert_eq!(g.snapshot(), saved);
+    }
+    assert_eq!(g.max_flow_warm(0, n - 1), Ok(total));
+    assert_eq!(g.edges_with_flow().collect::<Vec<_>>(), copy.edges_with_flow().collect::<Vec<_>>());
+    // 删除和添加的边以及供给都可以回滚
+    let mut h = g.clone();
+    for &id in &ids[..5] {
+        h.remove_edge(id).unwrap();
+    }
+    h.add_edge(edges[0].0, edges[0].1, &edges[0].2).unwrap();
+    h.set_supply(1, &4).unwrap();
+    h.max_flow_warm(0, n - 1).unwrap();
+    let changed = h.snapshot();
+    h.restore(&saved).unwrap();
+    assert_eq!(h.snapshot(), saved);
+    assert_eq!(h.get_supply(1), Ok(0));
+    assert_eq!(h.edges_with_flow().collect::<Vec<_>>(), copy.edges_with_flow().collect::<Vec<_>>());
+    h.reset_flow();
+    assert_eq!(h.mcmf(0, n - 1), build(n, &edges).mcmf(0, n - 1));
+    // 也可以恢复到之后的快照
+    h.restore(&changed).unwrap();
+    assert_eq!(h.snapshot(), changed);
+    assert_eq!(h.get_flow(ids[0]), Err(FlowError::InvalidEdge(ids[0].index())));
+    // 克隆的图与原图互不影响
+    let mut h = g.clone();
+    h.remove_node(0).unwrap();
+    h.reset_flow();
+    assert_eq!(g.max_flow_warm(0, n - 1), Ok(total));
+    assert_eq!(h.restore(&saved), Err(FlowError::UnsupportedGraph(String::from("graph structure changed since snapshot"))));
+}
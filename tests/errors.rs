@@ -0,0 +1,123 @@
+use network_flow::error::FlowError;
+use network_flow::graph::Graph;
+use network_flow::io::{BitIO, StrIO};
+use std::fs;
+
+fn temp_file(name : &str) -> String {
+    std::env::temp_dir().join(name).to_str().unwrap().to_string()
+}
+
+#[test]
+fn invalid_node() {
+    let mut g = Graph::<usize, u32, u32>::create_graph(&[0, 1]);
+    assert_eq!(g.add_edge(0, 5, &1), Err(FlowError::InvalidNode(5)));
+    assert_eq!(g.get_max_flow(2, 0), Err(FlowError::InvalidNode(2)));
+    assert_eq!(g.mcmf(0, 3), Err(FlowError::InvalidNode(3)));
+    assert_eq!(g.get_cut(7), Err(FlowError::InvalidNode(7)));
+    assert_eq!(g.set_supply(9, &1), Err(FlowError::InvalidNode(9)));
+    assert!(g.first_edge(4).is_none());
+    assert_eq!(g.get_max_flow_by_label(&0, &4), Err(FlowError::UnknownLabel));
+    assert_eq!(g.remove_node(2), Err(FlowError::InvalidNode(2)));
+    let e = g.add_edge(0, 1, &1).unwrap();
+    assert_eq!(g.remove_edge(e), Ok(()));
+    assert_eq!(g.remove_edge(e), Err(FlowError::InvalidEdge(0)));
+    assert_eq!(g.get_flow(e), Err(FlowError::InvalidEdge(0)));
+    assert_eq!(g.get_endpoints(e), Err(FlowError::InvalidEdge(0)));
+}
+
+#[test]
+fn lower_above_upper() {
+    // 无符号类型不会因为upper - lower溢出，有符号类型也不会得到负的容量
+    let mut g = Graph::<usize, u32, u32>::create_graph(&[0, 1]);
+    assert_eq!(g.add_edge_bounded(0, 1, &3, &2, &0), Err(FlowError::Infeasible(vec![0, 1])));
+    assert_eq!(g.add_edge_bounded(0, 2, &3, &2, &0), Err(FlowError::InvalidNode(2)));
+    let mut h = Graph::<usize, i32, i32>::create_graph(&[0, 1]);
+    assert_eq!(h.add_edge_bounded(1, 0, &1, &-1, &0), Err(FlowError::Infeasible(vec![1, 0])));
+    assert_eq!(h.edges_with_flow().count(), 0);
+    assert!(h.add_edge_bounded(1, 0, &-1, &-1, &0).is_ok());
+}
+
+#[test]
+fn bad_values() {
+    assert_eq!(u32::from_str("12a"), Err(FlowError::Parse(String::from("12a"))));
+    assert_eq!(u64::from_bit(&[0, 1]), Err(FlowError::TruncatedInput));
+    let mut a = 2usize.to_bit();
+    a.extend_from_slice(&[0xff, 0xfe]);
+    assert!(matches!(String::from_bit(&a), Err(FlowError::MalformedFile(_))));
+    a.pop();
+    assert_eq!(String::from_bit(&a), Err(FlowError::TruncatedInput));
+}
+
+#[test]
+fn bad_files() -> Result<(), FlowError> {
+    let file = temp_file("network_flow_errors.bin");
+    let mut g = Graph::<String, u32, u32>::new();
+    g.add_node(&String::from("s"));
+    g.add_node(&String::from("t"));
+    g.add_edge(0, 1, &3)?;
+    g.set_supply(0, &2)?;
+    g.output_file(&file)?;
+    let data = fs::read(&file)?;
+    // 截断的文件
+    fs::write(&file, &data[..data.len() - 3])?;
+    assert_eq!(Graph::<String, u32, u32>::input_file(&file).err(), Some(FlowError::TruncatedInput));
+    // 修改最后一条边的下界，校验和不再一致
+    let mut broken = data.clone();
+    broken[data.len() - 9] ^= 1;
+    fs::write(&file, &broken)?;
+    assert_eq!(Graph::<String, u32, u32>::input_file(&file).err(),
+        Some(FlowError::MalformedFile(String::from("checksum mismatch"))));
+    broken = data.clone();
+    broken[0] = b'x';
+    assert_eq!(Graph::<String, u32, u32>::read_from(&broken[..]).err(),
+        Some(FlowError::MalformedFile(String::from("bad magic number"))));
+    broken = data.clone();
+    broken[11] = 2;
+    assert_eq!(Graph::<String, u32, u32>::read_from(&broken[..]).err(),
+        Some(FlowError::MalformedFile(String::from("unsupported version 2"))));
+    fs::write(&file, &data)?;
+    let mut g = Graph::<String, u32, u32>::input_file(&file)?;
+    assert_eq!(g.get_index(&String::from("t")), Some(1));
+    assert_eq!((g.get_supply(0), g.get_supply(1)), (Ok(2), Ok(0)));
+    assert_eq!((g.get_supply(2), g.get_demand(2)), (Err(FlowError::InvalidNode(2)), Err(FlowError::InvalidNode(2))));
+    assert_eq!(g.get_max_flow(0, 1)?, 3);
+    fs::write(&file, "digraph { 0 [label=1]; 1 [label=2]; 0 -> 1 [capacity=x] }")?;
+    assert_eq!(Graph::<usize, u32, u32>::from_dot(&file).err(), Some(FlowError::Parse(String::from("x"))));
+    fs::write(&file, "digraph { a:n -> b [capacity=1] }")?;
+    assert!(matches!(Graph::<usize, u32, u32>::from_dot(&file), Err(FlowError::UnsupportedGraph(_))));
+    fs::write(&file, "digraph { 0 -> 1 [cost=1] }")?;
+    assert!(matches!(Graph::<usize, u32, u32>::from_dot(&file), Err(FlowError::MalformedFile(_))));
+    fs::write(&file, "digraph { 0 -> 1 [capacity=1, flow=2] }")?;
+    assert!(matches!(Graph::<usize, u32, u32>::from_dot(&file), Err(FlowError::MalformedFile(_))));
+    fs::write(&file, "digraph { 0 [label=1]; 1 [label=1] }")?;
+    assert!(matches!(Graph::<usize, u32, u32>::from_dot(&file), Err(FlowError::MalformedFile(_))));
+    fs::remove_file(&file)?;
+    Ok(())
+}
+
+#[test]
+fn binary_self_loop() -> Result<(), FlowError> {
+    // 自环的正向边和被删除的边都不在邻接表中，删除点之后被删除的边的端点也可能已经失效
+    let mut g = Graph::<String, u32, u32>::new();
+    for label in ["s", "a", "t", "x"] {
+        g.add_node(&String::from(label));
+    }
+    let e = g.add_edge(0, 1, &2)?;
+    g.add_edge(1, 1, &5)?;
+    g.add_edge2(1, 2, &3, &1)?;
+    g.add_edge(0, 0, &1)?;
+    g.add_edge(1, 3, &4)?;
+    g.add_edge(0, 2, &1)?;
+    g.remove_edge(e)?;
+    g.remove_node(3)?;
+    g.add_edge(0, 1, &2)?;
+    g.mcmf(0, 2)?;
+    let mut buf = vec![];
+    g.write_to(&mut buf)?;
+    let mut h = Graph::<String, u32, u32>::read_from(&buf[..])?;
+    assert_eq!(h.edges_with_flow().collect::<Vec<_>>(), g.edges_with_flow().collect::<Vec<_>>());
+    h.reset_flow();
+    g.reset_flow();
+    assert_eq!(h.mcmf(0, 2), g.mcmf(0, 2));
+    Ok(())
+}
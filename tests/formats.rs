@@ -0,0 +1,128 @@
+#![cfg(any(feature = "json", feature = "graphml"))]
+
+use network_flow::costtype::MulTEDefaultType;
+use network_flow::error::FlowError;
+use network_flow::graph::Graph;
+#[cfg(feature = "graphml")]
+use network_flow::io::graphml::{read_graphml, write_graphml};
+#[cfg(feature = "json")]
+use network_flow::io::node_link::{read_node_link, write_node_link};
+
+mod common;
+use common::random_edges;
+
+fn random_graph(n : usize, m : usize, seed : u64) -> Graph<String, i64, i64> {
+    let labels : Vec<String> = (0..n).map(|i| format!("<node & \"{}\">", i)).collect();
+    let mut g = Graph::<String, i64, i64>::create_graph(&labels);
+    for (u, v, w, c) in random_edges(n, m, seed) {
+        if u != v {
+            g.add_edge_bounded(u, v, &(w / 3), &w, &(c - 5)).unwrap();
+        }
+    }
+    g.set_supply(0, &3).unwrap();
+    g
+}
+
+fn same_graph(g : &Graph<String, i64, i64>, h : &Graph<String, i64, i64>) {
+    assert_eq!(h.node_count(), g.node_count());
+    for i in 0..g.node_count() {
+        assert_eq!(h.get_label(i), g.get_label(i));
+        assert_eq!((h.get_supply(i), h.get_demand(i)), (g.get_supply(i), g.get_demand(i)));
+    }
+    assert_eq!(h.edges_with_flow().collect::<Vec<_>>(), g.edges_with_flow().collect::<Vec<_>>());
+    for (id, ..) in g.edges_with_flow() {
+        assert_eq!((h.get_capacity(id), h.get_lower(id), h.get_cost(id)), (g.get_capacity(id), g.get_lower(id), g.get_cost(id)));
+    }
+}
+
+#[test]
+#[cfg(feature = "graphml")]
+fn graphml_round_trip() {
+    for seed in 0..5 {
+        let mut g = random_graph(12, 40, seed);
+        g.get_max_flow(0, 11).unwrap();
+        let mut buf = vec![];
+        write_graphml(&g, &mut buf).unwrap();
+        same_graph(&g, &read_graphml(&buf[..]).unwrap());
+    }
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn node_link_round_trip() {
+    for seed in 0..5 {
+        let mut g = random_graph(12, 40, seed);
+        g.get_max_flow(0, 11).unwrap();
+        let mut buf = vec![];
+        write_node_link(&g, &mut buf).unwrap();
+        same_graph(&g, &read_node_link(&buf[..]).unwrap());
+    }
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn node_link_numeric_labels() {
+    let labels : Vec<String> = ["1e5", "1.50", "007", "-0", "3"].iter().map(|s| s.to_string()).collect();
+    let mut g = Graph::<String, i64, i64>::create_graph(&labels);
+    g.add_edge2(0, 4, &2, &1).unwrap();
+    g.add_edge2(1, 2, &1, &0).unwrap();
+    let mut buf = vec![];
+    write_node_link(&g, &mut buf).unwrap();
+    let s = String::from_utf8(buf.clone()).unwrap();
+    assert!(s.contains("\"1e5\"") && s.contains("\"1.50\"") && s.contains("\"007\""));
+    same_graph(&g, &read_node_link(&buf[..]).unwrap());
+}
+
+#[test]
+#[cfg(feature = "graphml")]
+fn graphml_foreign_file() {
+    // networkx输出的GraphML中key的id与属性名不同
+    let s = r#"<?xml version="1.0"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="d0" for="edge" attr.name="capacity" attr.type="long"/>
+  <key id="d1" for="edge" attr.name="weight" attr.type="long"><default>2</default></key>
+  <key id="d2" for="edge" attr.name="cost" attr.type="long"><default>1</default></key>
+  <graph edgedefault="undirected">
+    <edge source="a" target="b"><data key="d0">4</data></edge>
+    <node id="a"/><node id="b"/><node id="c"/>
+    <edge source="b" target="c" directed="true"><data key="d0">3</data><data key="d2">5</data></edge>
+  </graph>
+</graphml>"#;
+    let mut g : Graph<String, i32, i32> = read_graphml(s.as_bytes()).unwrap();
+    assert_eq!(g.edges_with_flow().count(), 3);
+    assert_eq!(g.mcmf(0, 2), Ok((3, 18)));
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn node_link_foreign_file() {
+    let s = r#"{"directed": false, "nodes": [{"id": "x"}, {"id": 7, "label": "y", "demand": 2}],
+        "edges": [{"source": "x", "target": 7, "capacity": 2, "weight": 9}]}"#;
+    let mut g : Graph<String, i32, i32> = read_node_link(s.as_bytes()).unwrap();
+    assert_eq!((g.get_index(&String::from("y")), g.get_demand(1)), (Some(1), Ok(2)));
+    assert_eq!(g.get_max_flow(1, 0), Ok(2));
+}
+
+#[test]
+#[cfg(feature = "graphml")]
+fn graphml_errors() {
+    let graphml = |s : &str| read_graphml::<String, i32, i32, MulTEDefaultType, _>(s.as_bytes()).err();
+    assert!(matches!(graphml("<graphml><graph>"), Some(FlowError::Parse(_))));
+    assert!(matches!(graphml("<graphml><graph><node id=\"a\"><graph/></node></graph></graphml>"),
+        Some(FlowError::UnsupportedGraph(_))));
+    assert_eq!(graphml("<graphml><graph><node id=\"a\"/><edge source=\"a\" target=\"b\"/></graph></graphml>"),
+        Some(FlowError::MalformedFile(String::from("unknown node b"))));
+    assert_eq!(graphml("<graphml><graph><node id=\"a\"/><edge source=\"a\" target=\"a\"/></graph></graphml>"),
+        Some(FlowError::MalformedFile(String::from("edge without capacity"))));
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn node_link_errors() {
+    let node_link = |s : &str| read_node_link::<String, i32, i32, MulTEDefaultType, _>(s.as_bytes()).err();
+    assert_eq!(node_link("{\"nodes\": [{\"id\": 1}"), Some(FlowError::TruncatedInput));
+    assert_eq!(node_link("{\"nodes\": [{\"id\": 1}, {\"id\": 1}], \"links\": []}"),
+        Some(FlowError::MalformedFile(String::from("duplicate node label"))));
+    assert_eq!(node_link("{\"nodes\": [{\"id\": 1}, {\"id\": 2}], \"links\": [{\"source\": 1, \"target\": 2, \"capacity\": \"x\"}]}"),
+        Some(FlowError::Parse(String::from("x"))));
+}
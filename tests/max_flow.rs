@@ -0,0 +1,91 @@
+use network_flow::graph::{Graph, MaxFlowAlgorithm};
+use std::collections::HashMap;
+
+mod common;
+use common::random_graph;
+
+#[test]
+fn push_relabel_small() {
+    let mut g = Graph::<String, u32, u32>::new();
+    for i in 1..7 {
+        g.add_node(&format!("astesia{}", i));
+    }
+    g.add_edge(0, 1, &10).unwrap();
+    g.add_edge(0, 2, &3).unwrap();
+    g.add_edge(1, 2, &2).unwrap();
+    g.add_edge(1, 3, &5).unwrap();
+    g.add_edge(2, 4, &7).unwrap();
+    g.add_edge(3, 4, &1).unwrap();
+    g.add_edge(3, 5, &3).unwrap();
+    g.add_edge(4, 5, &9).unwrap();
+    assert_eq!(9, g.max_flow_with(0, 5, MaxFlowAlgorithm::PushRelabel).unwrap());
+    assert_eq!(0, g.max_flow_with(0, 5, MaxFlowAlgorithm::Dinic).unwrap());
+}
+
+#[test]
+fn push_relabel_edge_cases() {
+    // 平行边、自环、容量为0的边，以及推入后必须退回源点的死胡同
+    let mut g = Graph::<usize, u32>::create_graph(&[0, 1, 2, 3, 4, 5]);
+    let a = g.add_edge(0, 1, &5).unwrap();
+    let b = g.add_edge(0, 1, &2).unwrap();
+    let l = g.add_edge(1, 1, &4).unwrap();
+    let z = g.add_edge(1, 2, &0).unwrap();
+    g.add_edge(1, 3, &3).unwrap();
+    g.add_edge(3, 2, &10).unwrap();
+    let d = g.add_edge(0, 4, &6).unwrap();
+    assert_eq!(g.max_flow_with(0, 2, MaxFlowAlgorithm::PushRelabel), Ok(3));
+    assert_eq!(g.get_flow(a).unwrap() + g.get_flow(b).unwrap(), 3);
+    assert_eq!((g.get_flow(l), g.get_flow(z), g.get_flow(d)), (Ok(0), Ok(0), Ok(0)));
+    assert_eq!(g.get_cut(0), Ok(vec![0, 1, 4]));
+    assert_eq!(g.max_flow_with(0, 2, MaxFlowAlgorithm::Dinic), Ok(0));
+    // 汇点不可达或与源点相同
+    assert_eq!(g.max_flow_with(0, 5, MaxFlowAlgorithm::PushRelabel), Ok(0));
+    assert_eq!(g.max_flow_with(3, 3, MaxFlowAlgorithm::PushRelabel), Ok(0));
+}
+
+#[test]
+fn dinic_long_path() {
+    // 很长的分层路径，递归实现的dfs会栈溢出
+    let n = 200000;
+    let mut g = Graph::<usize, u32>::create_graph(&(0..n).collect::<Vec<_>>());
+    for i in 0..n - 1 {
+        g.add_edge(i, i + 1, &(3 + (i % 7) as u32)).unwrap();
+        if i + 2 < n {
+            g.add_edge(i, i + 2, &1).unwrap();
+        }
+    }
+    assert_eq!(g.get_max_flow(0, n - 1).unwrap(), 4);
+    assert_eq!(g.get_max_flow(0, n - 1).unwrap(), 0);
+}
+
+#[test]
+fn decompose_max_flow() {
+    for seed in 0..50 {
+        let n = 2 + seed as usize % 30;
+        let mut g = random_graph(n, n * 4, seed);
+        let f = g.get_max_flow(0, n - 1).unwrap();
+        let d = g.decompose_flow(0, n - 1).unwrap();
+        assert_eq!(d.paths.iter().map(|p| p.flow).sum::<i64>(), f);
+        for p in d.paths.iter().chain(d.cycles.iter()) {
+            assert!(p.flow > 0);
+            for (k, &e) in p.edges.iter().enumerate() {
+                assert_eq!(g.get_endpoints(e).unwrap().1, p.nodes[(k + 1) % p.nodes.len()]);
+            }
+        }
+        // 每条边上分解出的流量之和等于边上的流量
+        let mut used = HashMap::new();
+        for p in d.paths.iter().chain(d.cycles.iter()) {
+            for &e in &p.edges {
+                *used.entry(e).or_insert(0) += p.flow;
+            }
+        }
+        for (e, from, to, f) in g.edges_with_flow() {
+            assert_eq!(used.get(&e).copied().unwrap_or(0), f);
+            assert_eq!(g.get_endpoints(e), Ok((from, to)));
+            assert_eq!(g.get_flow(e).unwrap() + g.get_residual(e).unwrap(), g.get_capacity(e).unwrap());
+        }
+        for p in &d.paths {
+            assert_eq!((p.nodes[0], *p.nodes.last().unwrap()), (0, n - 1));
+        }
+    }
+}
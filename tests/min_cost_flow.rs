@@ -0,0 +1,352 @@
+use network_flow::error::FlowError;
+use network_flow::graph::{Graph, McmfAlgorithm};
+
+mod common;
+use common::{random_graph, Lcg};
+
+#[test]
+fn mcmf_cancels_reverse_arc_cost() {
+    // 第二条增广路经过a->b的反向边，其费用应当从总费用中减去
+    let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2, 3]);
+    g.add_edge2(0, 1, &1, &1).unwrap();
+    g.add_edge2(1, 2, &1, &1).unwrap();
+    g.add_edge2(2, 3, &1, &1).unwrap();
+    g.add_edge2(0, 2, &1, &3).unwrap();
+    g.add_edge2(1, 3, &1, &3).unwrap();
+    assert_eq!(g.mcmf(0, 3), Ok((2, 8)));
+}
+
+#[test]
+fn primal_dual_edge_cases() {
+    // 平行边、负费用的自环和容量为0的负费用边都不应影响结果
+    let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2, 3]);
+    let a = g.add_edge2(0, 1, &2, &1).unwrap();
+    let b = g.add_edge2(0, 1, &1, &4).unwrap();
+    let l = g.add_edge2(1, 1, &5, &-10).unwrap();
+    let z = g.add_edge2(0, 2, &0, &-100).unwrap();
+    g.add_edge2(1, 2, &5, &2).unwrap();
+    assert_eq!(g.mcmf_with(0, 2, McmfAlgorithm::PrimalDual), Ok((3, 12)));
+    assert_eq!((g.get_flow(a), g.get_flow(b), g.get_flow(l), g.get_flow(z)), (Ok(2), Ok(1), Ok(0), Ok(0)));
+    // 汇点不可达或与源点相同
+    assert_eq!(g.mcmf_with(0, 3, McmfAlgorithm::PrimalDual), Ok((0, 0)));
+    assert_eq!(g.mcmf_with(1, 1, McmfAlgorithm::PrimalDual), Ok((0, 0)));
+    assert_eq!(g.mcmf_with(0, 4, McmfAlgorithm::PrimalDual), Err(FlowError::InvalidNode(4)));
+}
+
+#[test]
+fn primal_dual_negative_cycle() {
+    let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2, 3]);
+    g.add_edge2(0, 1, &1, &1).unwrap();
+    g.add_edge2(1, 2, &1, &-5).unwrap();
+    g.add_edge2(2, 1, &1, &1).unwrap();
+    g.add_edge2(1, 3, &1, &1).unwrap();
+    let r = g.mcmf_with(0, 3, McmfAlgorithm::PrimalDual);
+    assert!(matches!(r, Err(FlowError::NegativeCycle(ref c)) if c.len() == 2 && c.contains(&1) && c.contains(&2)), "{:?}", r);
+    assert_eq!(g.mcmf_cycle_canceling(0, 3), Ok((1, -2)));
+}
+
+#[test]
+fn primal_dual_negative_cost() {
+    // 没有负环的负费用边
+    let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2, 3]);
+    g.add_edge2(0, 1, &2, &-3).unwrap();
+    g.add_edge2(0, 2, &2, &1).unwrap();
+    g.add_edge2(1, 3, &1, &2).unwrap();
+    g.add_edge2(2, 3, &3, &-1).unwrap();
+    g.add_edge2(1, 2, &1, &-2).unwrap();
+    assert_eq!(g.mcmf_with(0, 3, McmfAlgorithm::PrimalDual), Ok((4, -7)));
+}
+
+#[test]
+fn mcmf_with_float_cost() {
+    // 选择算法时不要求费用是整数
+    let build = || {
+        let mut g = Graph::<usize, u32, f64>::create_graph(&[0, 1, 2]);
+        g.add_edge2(0, 1, &2, &0.5).unwrap();
+        g.add_edge2(1, 2, &1, &1.5).unwrap();
+        g.add_edge2(0, 2, &1, &3.0).unwrap();
+        g
+    };
+    for algorithm in [McmfAlgorithm::Spfa, McmfAlgorithm::PrimalDual, McmfAlgorithm::CycleCanceling] {
+        assert_eq!(build().mcmf_with(0, 2, algorithm), Ok((2, 5.0)));
+    }
+}
+
+#[test]
+fn network_simplex_negative_cycles() {
+    // 负费用的环和自环在最小费用流中会被推满，容量为0的边不会有流量
+    let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2]);
+    let a = g.add_edge2(0, 1, &2, &1).unwrap();
+    let b = g.add_edge2(1, 2, &2, &1).unwrap();
+    let c = g.add_edge2(2, 1, &1, &-3).unwrap();
+    let l = g.add_edge2(1, 1, &5, &-2).unwrap();
+    let z = g.add_edge2(0, 2, &0, &-9).unwrap();
+    let (cost, h) = g.network_simplex(&[1], &[0, 0, 1]).unwrap();
+    assert_eq!(cost, -10);
+    assert_eq!([a, b, c, l, z].map(|e| g.get_flow(e).unwrap()), [1, 2, 1, 5, 0]);
+    for (e, from, to, f) in g.edges_with_flow() {
+        let reduced = g.get_cost(e).unwrap() + h[from] - h[to];
+        assert!((f == g.get_capacity(e).unwrap() || reduced >= 0) && (f == 0 || reduced <= 0));
+    }
+    // 没有供需时只剩下负环
+    assert_eq!(g.network_simplex(&[], &[]).map(|r| r.0), Ok(-12));
+    assert_eq!(g.get_flow(a), Ok(0));
+}
+
+#[test]
+fn network_simplex_transportation() {
+    // 两个仓库，三个商店
+    let mut g = Graph::<usize, u32, i64>::create_graph(&[0, 1, 2, 3, 4]);
+    let cost = [[4, 6, 9], [5, 3, 8]];
+    for (i, row) in cost.iter().enumerate() {
+        for (j, c) in row.iter().enumerate() {
+            g.add_edge2(i, j + 2, &u32::MAX, c).unwrap();
+        }
+    }
+    let (c, h) = g.network_simplex(&[30, 25, 0, 0, 0], &[0, 0, 15, 20, 20]).unwrap();
+    assert_eq!(c, 15 * 4 + 20 * 3 + 15 * 9 + 5 * 8);
+    assert_eq!(h.len(), 5);
+    assert_eq!(g.network_simplex(&[30, 25, 0, 0, 0], &[0, 0, 15, 20, 30]), Err(FlowError::Infeasible(vec![4])));
+}
+
+#[test]
+fn infeasible_keeps_flow() {
+    let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2]);
+    let a = g.add_edge2(0, 2, &2, &1).unwrap();
+    let b = g.add_edge2(2, 1, &3, &1).unwrap();
+    let c = g.add_edge2(0, 1, &2, &5).unwrap();
+    g.set_supply(0, &3).unwrap();
+    g.set_demand(1, &3).unwrap();
+    assert_eq!(g.min_cost_flow(), Ok(9));
+    let before : Vec<_> = g.edges_with_flow().collect();
+    g.set_supply(0, &5).unwrap();
+    g.set_demand(1, &5).unwrap();
+    assert_eq!(g.min_cost_flow(), Err(FlowError::Infeasible(vec![0, 1])));
+    assert_eq!(g.edges_with_flow().collect::<Vec<_>>(), before);
+    assert_eq!((g.get_flow(a), g.get_flow(b), g.get_flow(c)), (Ok(2), Ok(2), Ok(1)));
+}
+
+#[test]
+fn cost_scaling_edge_cases() {
+    // 负环会被消去，自环、容量为0的边和较贵的平行边不会有流量
+    let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2, 3]);
+    g.add_edge2(0, 1, &1, &1).unwrap();
+    g.add_edge2(1, 2, &1, &-5).unwrap();
+    g.add_edge2(2, 1, &1, &1).unwrap();
+    g.add_edge2(1, 3, &1, &1).unwrap();
+    let l = g.add_edge2(3, 3, &4, &-7).unwrap();
+    let z = g.add_edge2(0, 3, &0, &-9).unwrap();
+    let p = g.add_edge2(0, 1, &1, &3).unwrap();
+    let (f, c, h) = g.mcmf_cost_scaling(0, 3).unwrap();
+    assert_eq!((f, c), (1, -2));
+    assert_eq!([l, z, p].map(|e| g.get_flow(e).unwrap()), [0, 0, 0]);
+    for (e, from, to, _) in g.edges_with_flow() {
+        if g.get_residual(e).unwrap() > 0 && from != to {
+            assert!(g.get_cost(e).unwrap() + h[from] - h[to] >= 0);
+        }
+    }
+    assert_eq!(g.mcmf_cost_scaling(2, 2).map(|r| (r.0, r.1)), Ok((0, 0)));
+}
+
+#[test]
+fn cost_scaling_overflow_keeps_graph() {
+    // 总费用200无法用i8表示，返回错误时图中仍然是零流
+    let mut g = Graph::<usize, i8, i8>::create_graph(&[0, 1, 2]);
+    g.add_edge2(0, 1, &2, &50).unwrap();
+    g.add_edge2(1, 2, &2, &50).unwrap();
+    assert_eq!(g.mcmf_cost_scaling(0, 2), Err(FlowError::Overflow));
+    assert!(g.edges_with_flow().all(|x| x.3 == 0));
+    assert_eq!(g.get_max_flow(0, 2), Ok(2));
+}
+
+#[test]
+fn min_cost_flow_balances() {
+    let mut g = Graph::<String, i32, i32>::new();
+    g.set_supply_by_label(&String::from("a"), &4).unwrap();
+    g.set_supply_by_label(&String::from("b"), &2).unwrap();
+    g.set_demand_by_label(&String::from("c"), &3).unwrap();
+    g.set_demand_by_label(&String::from("d"), &3).unwrap();
+    g.add_edge2(0, 2, &3, &1).unwrap();
+    g.add_edge2(0, 3, &3, &4).unwrap();
+    g.add_edge2(1, 2, &2, &2).unwrap();
+    g.add_edge2(1, 3, &2, &1).unwrap();
+    assert_eq!(g.get_supply(0), Ok(4));
+    assert_eq!(g.get_demand(3), Ok(3));
+    assert_eq!(g.min_cost_flow(), Ok(3 + 4 + 2));
+    // 供给小于需求时无解
+    g.set_supply(1, &1).unwrap();
+    let res = g.min_cost_flow();
+    assert!(res.is_err());
+}
+
+#[test]
+fn bounded_edge_cases() {
+    // 上下界相等的边、容量为0的边和带下界的自环
+    let mut g = Graph::<usize, i64, i64>::create_graph(&[0, 1, 2, 3]);
+    let a = g.add_edge_bounded(0, 1, &0, &2, &1).unwrap();
+    let b = g.add_edge_bounded(0, 2, &1, &1, &4).unwrap();
+    let z = g.add_edge_bounded(0, 2, &0, &0, &-9).unwrap();
+    let l = g.add_edge_bounded(1, 1, &2, &3, &1).unwrap();
+    g.add_edge_bounded(1, 2, &0, &5, &1).unwrap();
+    // 2 * (1 + 1) + 4 + 自环下界上的2
+    assert_eq!(g.mcmf_bounded(0, 2), Ok((3, 10)));
+    assert_eq!([a, b, z, l].map(|e| g.get_flow(e).unwrap()), [2, 1, 0, 2]);
+    // 固定的1单位流量到达2后无法再流出
+    assert_eq!(g.mcmf_bounded(0, 3), Err(FlowError::Infeasible(vec![2])));
+}
+
+#[test]
+fn bounded_forces_expensive_edge() {
+    // 下界迫使流量经过费用更大的边，并且需要一个循环才能满足
+    let mut g = Graph::<usize, i64, i64>::create_graph(&[0, 1, 2, 3]);
+    g.add_edge_bounded(0, 1, &0, &3, &1).unwrap();
+    g.add_edge_bounded(1, 3, &0, &3, &1).unwrap();
+    let a = g.add_edge_bounded(0, 2, &2, &3, &5).unwrap();
+    g.add_edge_bounded(2, 3, &0, &1, &5).unwrap();
+    let b = g.add_edge_bounded(2, 1, &0, &3, &0).unwrap();
+    // 0->2和0->1各2单位，其中1单位经过2->1
+    assert_eq!(g.mcmf_bounded(0, 3), Ok((4, 20)));
+    assert_eq!((g.get_flow(a), g.get_lower(a), g.get_capacity(a), g.get_residual(a)), (Ok(2), Ok(2), Ok(3), Ok(1)));
+    assert_eq!((g.get_flow(b), g.get_cost(b)), (Ok(1), Ok(0)));
+    let mut g = Graph::<usize, i64, i64>::create_graph(&[0, 1, 2, 3]);
+    g.add_edge_bounded(0, 1, &0, &3, &1).unwrap();
+    g.add_edge_bounded(1, 3, &0, &3, &1).unwrap();
+    g.add_edge_bounded(0, 2, &2, &3, &5).unwrap();
+    g.add_edge_bounded(2, 3, &0, &1, &5).unwrap();
+    assert_eq!(g.max_flow_bounded(0, 3), Err(FlowError::Infeasible(vec![2])));
+    assert_eq!(g.get_max_flow(0, 3).unwrap(), 4);
+}
+
+#[test]
+fn bounded_negative_costs() {
+    // 从s到t的负费用路径不是负环，与没有下界时的结果相同
+    let mut g = Graph::<usize, i64, i64>::create_graph(&[0, 1, 2]);
+    let a = g.add_edge_bounded(0, 1, &1, &2, &-1).unwrap();
+    g.add_edge_bounded(1, 2, &0, &2, &-1).unwrap();
+    assert_eq!(g.mcmf_bounded(0, 2), Ok((2, -4)));
+    assert_eq!(g.get_flow(a), Ok(2));
+    // 真正的负环会被消去，下界迫使1单位流量经过费用为3的边
+    let mut g = Graph::<usize, i64, i64>::create_graph(&[0, 1, 2, 3]);
+    g.add_edge_bounded(0, 1, &0, &1, &1).unwrap();
+    g.add_edge_bounded(1, 3, &1, &1, &3).unwrap();
+    let b = g.add_edge_bounded(1, 2, &0, &2, &-3).unwrap();
+    g.add_edge_bounded(2, 1, &0, &2, &1).unwrap();
+    assert_eq!(g.mcmf_bounded(0, 3), Ok((1, 0)));
+    assert_eq!(g.get_flow(b), Ok(2));
+}
+
+#[test]
+fn min_cost_flow_k_same_as_limited_source() {
+    for seed in 0..30 {
+        let n = 2 + seed as usize % 20;
+        for k in [0, 1, 3, 7, 100] {
+            let mut g1 = random_graph(n, n * 4, seed);
+            // 添加一个新的源点，通过容量为k的边限制流量
+            let mut g2 = random_graph(n, n * 4, seed);
+            g2.add_node(&n);
+            g2.add_edge2(n, 0, &k, &0).unwrap();
+            assert_eq!(g1.min_cost_flow_k(0, n - 1, &k), g2.mcmf(n, n - 1));
+        }
+    }
+}
+
+#[test]
+fn min_cost_flow_any_is_minimum() {
+    for seed in 0..30u64 {
+        let n = 2 + seed as usize % 15;
+        // 只有从小编号指向大编号的边可以为负费用，保证没有负环
+        let mut rng = Lcg(seed);
+        let mut edges = vec![];
+        for _ in 0..n * 4 {
+            let u = rng.next() % n;
+            let v = rng.next() % n;
+            let w = (rng.next() % 10) as i64;
+            let c = (rng.next() % 20) as i64 - 10;
+            if u < v {
+                edges.push((u, v, w, c));
+            }
+        }
+        let build = || {
+            let mut g = Graph::<usize, i64, i64>::create_graph(&(0..n).collect::<Vec<_>>());
+            for (u, v, w, c) in &edges {
+                g.add_edge2(*u, *v, w, c).unwrap();
+            }
+            g
+        };
+        let (flow, cost) = build().min_cost_flow_any(0, n - 1).unwrap();
+        let (max_flow, _) = build().mcmf(0, n - 1).unwrap();
+        for k in 0..=max_flow {
+            let (_, c) = build().min_cost_flow_k(0, n - 1, &k).unwrap();
+            assert!(cost <= c);
+        }
+        assert_eq!(build().min_cost_flow_k(0, n - 1, &flow), Ok((flow, cost)));
+    }
+}
+
+#[test]
+fn cycle_canceling_edge_cases() {
+    // 不在s到t路径上的负环也会被消去并计入费用，自环和容量为0的边没有流量
+    let mut g = Graph::<usize, i32, i32>::create_graph(&[0, 1, 2, 3, 4]);
+    let a = g.add_edge2(0, 1, &2, &3).unwrap();
+    let b = g.add_edge2(0, 1, &2, &1).unwrap();
+    g.add_edge2(1, 2, &3, &1).unwrap();
+    let c = g.add_edge2(3, 4, &2, &-2).unwrap();
+    let d = g.add_edge2(4, 3, &1, &1).unwrap();
+    let l = g.add_edge2(2, 2, &5, &-4).unwrap();
+    let z = g.add_edge2(2, 0, &0, &-9).unwrap();
+    assert_eq!(g.mcmf_cycle_canceling(0, 2), Ok((3, 8 - 1)));
+    assert_eq!([a, b, c, d, l, z].map(|e| g.get_flow(e).unwrap()), [1, 2, 1, 1, 0, 0]);
+    // 已经没有负环
+    assert_eq!(g.min_cost_circulation(), 0);
+    assert_eq!(g.mcmf_cycle_canceling(1, 1), Ok((0, 0)));
+}
+
+#[test]
+fn negative_cycle() {
+    for seed in 0..30 {
+        let n = 3 + seed as usize % 20;
+        // 加入一个费用为负的环0 -> 1 -> 2 -> 0
+        let build = || {
+            let mut g = random_graph(n, n * 4, seed);
+            g.add_edge2(0, 1, &3, &-10).unwrap();
+            g.add_edge2(1, 2, &3, &-10).unwrap();
+            g.add_edge2(2, 0, &3, &-10).unwrap();
+            g
+        };
+        let mut g1 = build();
+        let mut g2 = build();
+        let (f, c, _) = g2.mcmf_cost_scaling(0, n - 1).unwrap();
+        assert_eq!(g1.mcmf_cycle_canceling(0, n - 1).unwrap(), (f, c));
+        assert!(build().mcmf(0, n - 1).is_err());
+        assert!(build().mcmf_with(0, n - 1, McmfAlgorithm::PrimalDual).is_err());
+        // 消去负环之后可以正常求解
+        let mut g3 = build();
+        let c1 = g3.min_cost_circulation();
+        let (f2, c2) = g3.mcmf(0, n - 1).unwrap();
+        assert_eq!((f2, c1 + c2), (f, c));
+    }
+}
+
+#[test]
+fn decompose_min_cost_flow() {
+    for seed in 0..30 {
+        let n = 4 + seed as usize % 20;
+        let mut g = random_graph(n, n * 4, seed);
+        g.add_edge2(0, 1, &3, &-10).unwrap();
+        g.add_edge2(1, 2, &3, &-10).unwrap();
+        g.add_edge2(2, 0, &3, &-10).unwrap();
+        // 与s和t都不连通的负环，一定会被分解为环
+        for i in 0..3 {
+            g.add_node(&(n + i));
+        }
+        g.add_edge2(n, n + 1, &2, &-1).unwrap();
+        g.add_edge2(n + 1, n + 2, &2, &-1).unwrap();
+        g.add_edge2(n + 2, n, &2, &-1).unwrap();
+        let (f, c) = g.mcmf_cycle_canceling(0, n - 1).unwrap();
+        let d = g.decompose_flow(0, n - 1).unwrap();
+        assert_eq!(d.paths.iter().map(|p| p.flow).sum::<i64>(), f);
+        let total = d.paths.iter().chain(d.cycles.iter()).map(|p| p.cost).sum::<i64>();
+        assert_eq!(total, c);
+        assert!(d.cycles.iter().any(|p| p.nodes == vec![n, n + 1, n + 2] && p.flow == 2 && p.cost == -6));
+    }
+}
@@ -17,18 +17,18 @@ fn p1251() {
     let s = 0;
     let t = 2*n+2;
     for i in 1..n+1 {
-        g.add_edge2(s, i, &i32::MAX, &a);
-        g.add_edge2(i, t, &arr[i-1], &0);
-        g.add_edge2(s, i+n, &arr[i-1], &0);
-        g.add_edge2(i+n, i+n+1, &i32::MAX, &0);
+        g.add_edge2(s, i, &i32::MAX, &a).unwrap();
+        g.add_edge2(i, t, &arr[i-1], &0).unwrap();
+        g.add_edge2(s, i+n, &arr[i-1], &0).unwrap();
+        g.add_edge2(i+n, i+n+1, &i32::MAX, &0).unwrap();
         if i+b<=n {
-            g.add_edge2(i+n, i+b, &i32::MAX, &c);
+            g.add_edge2(i+n, i+b, &i32::MAX, &c).unwrap();
         }
         if i+d<=n {
-            g.add_edge2(i+n, i+d, &i32::MAX, &e);
+            g.add_edge2(i+n, i+d, &i32::MAX, &e).unwrap();
         }
     }
-    let (_, c) = g.mcmf(s, t);
+    let (_, c) = g.mcmf(s, t).unwrap();
     assert_eq!(c, 134);
 }
 
@@ -61,7 +61,7 @@ fn p2754() {
             c[i] = temp;
             g.add_node(&temp);
             temp = temp + 1;
-            g.add_edge(las[i], c[i], &i32::MAX);
+            g.add_edge(las[i], c[i], &i32::MAX).unwrap();
         }
         for i in 0..m {
             let y = arr[i][0];
@@ -70,9 +70,9 @@ fn p2754() {
             let v = arr[i][x];
             let u = (u+(n as i32)+2)as usize % (n+2);
             let v = (v+(n as i32)+2)as usize % (n+2);
-            g.add_edge(las[u], c[v], &r[i]);
+            g.add_edge(las[u], c[v], &r[i]).unwrap();
         }
-        flag += g.get_max_flow(s, t);
+        flag += g.get_max_flow(s, t).unwrap();
         if flag >= k {
             break;
         }
@@ -93,20 +93,20 @@ fn p2756() {
     let t = n + m + 1;
     let mut g = Graph::<usize, i32>::create_graph(&vec![0;t+2]);
     for i in 1..n+1 {
-        g.add_edge(s, i, &1);
+        g.add_edge(s, i, &1).unwrap();
     }
     for i in n+1..t {
-        g.add_edge(i, t, &1);
+        g.add_edge(i, t, &1).unwrap();
     }
     let p = [(1, 7), (1, 8), (2, 6), (2, 9), (2, 10), 
                                (3, 7), (3, 8), (4, 7), (4, 8), (5, 10)];
     for (u, v) in p {
-        g.add_edge(u, v, &1);
+        g.add_edge(u, v, &1).unwrap();
     }
-    assert_eq!(4, g.get_max_flow(s, t));
+    assert_eq!(4, g.get_max_flow(s, t).unwrap());
     let mut res = vec![];
     for i in 1..n+1 {
-        let e = g.get_all_edges(i);
+        let e = g.get_all_edges(i).unwrap();
         for (edge, _) in e {
             if edge.is_full() && !edge.is_reversed() {
                 res.push((i, edge.get_to()));
@@ -149,18 +149,18 @@ fn p2762() {
     let mut sum = 0;
     for i in 0..n {
         sum += arr[i][0];
-        g.add_edge(s, i + 1, &arr[i][0]);
+        g.add_edge(s, i + 1, &arr[i][0]).unwrap();
         for j in &arr[i][1..] {
-            g.add_edge(i + 1, *j+n, &usize::MAX);
+            g.add_edge(i + 1, *j+n, &usize::MAX).unwrap();
         }
 
     }
     let arr = [5, 6, 7];
     for i in 0..m {
-        g.add_edge(i+n+1, t, &arr[i]);
+        g.add_edge(i+n+1, t, &arr[i]).unwrap();
     }
-    assert_eq!(sum - g.get_max_flow(s, t), 17);
-    let v1 = g.get_cut(s);
+    assert_eq!(sum - g.get_max_flow(s, t).unwrap(), 17);
+    let v1 = g.get_cut(s).unwrap();
     let mut r1 = vec![];
     let mut r2 = vec![];
     for i in v1 {
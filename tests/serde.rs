@@ -0,0 +1,44 @@
+#![cfg(feature = "serde")]
+
+use network_flow::graph::decomposition::FlowDecomposition;
+use network_flow::graph::Graph;
+
+#[test]
+fn json_round_trip() {
+    let mut g = Graph::<String, i32, i32>::new();
+    g.set_supply_by_label(&String::from("s"), &2).unwrap();
+    let a = g.add_edge2_by_label(&String::from("s"), &String::from("a"), &3, &1);
+    g.add_edge2_by_label(&String::from("a"), &String::from("t"), &2, &2);
+    g.add_edge2_by_label(&String::from("s"), &String::from("t"), &1, &5);
+    g.add_edge_bounded(1, 2, &1, &4, &3).unwrap();
+    assert_eq!(g.mcmf(0, 2), Ok((4, 15)));
+    let json = serde_json::to_string(&g).unwrap();
+    let mut h : Graph<String, i32, i32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(h.get_index(&String::from("t")), Some(2));
+    assert_eq!(h.get_supply(0), Ok(2));
+    let b : Vec<_> = h.edges_with_flow().collect();
+    assert_eq!(b, g.edges_with_flow().collect::<Vec<_>>());
+    assert_eq!((h.get_flow(a), h.get_lower(b[3].0), h.get_capacity(b[3].0)), (Ok(3), Ok(1), Ok(4)));
+    // 读入的流量之后仍然可以继续增广
+    assert_eq!(h.mcmf(0, 2), Ok((0, 0)));
+    let d = g.decompose_flow(0, 2).unwrap();
+    let json = serde_json::to_string(&d).unwrap();
+    assert_eq!(serde_json::from_str::<FlowDecomposition<i32, i32>>(&json).unwrap(), d);
+}
+
+#[test]
+fn json_defaults_and_errors() {
+    let json = r#"{"nodes": [{"label": 0}, {"label": 1, "demand": 2}],
+        "edges": [{"from": 0, "to": 1, "capacity": 5, "cost": 3}]}"#;
+    let mut g : Graph<usize, u32, u32> = serde_json::from_str(json).unwrap();
+    assert_eq!((g.get_supply(0), g.get_demand(1)), (Ok(0), Ok(2)));
+    assert_eq!(g.mcmf(0, 1), Ok((5, 15)));
+    let bad = [
+        r#"{"nodes": [{"label": 0}, {"label": 0}], "edges": []}"#,
+        r#"{"nodes": [{"label": 0}], "edges": [{"from": 0, "to": 1, "capacity": 5}]}"#,
+        r#"{"nodes": [{"label": 0}, {"label": 1}], "edges": [{"from": 0, "to": 1, "capacity": 5, "flow": 6}]}"#
+    ];
+    for s in bad {
+        assert!(serde_json::from_str::<Graph<usize, u32, u32>>(s).is_err());
+    }
+}
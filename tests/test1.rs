@@ -9,13 +9,13 @@ fn main() {
     g.add_node(&String::from("astesia4"));
     g.add_node(&String::from("astesia5"));
     g.add_node(&String::from("astesia6"));
-    g.add_edge(0, 1, &10);
-    g.add_edge(0, 2, &3);
-    g.add_edge(1, 2, &2);
-    g.add_edge(1, 3, &5);
-    g.add_edge(2, 4, &7);
-    g.add_edge(3, 4, &1);
-    g.add_edge(3, 5, &3);
-    g.add_edge(4, 5, &9);
-    assert_eq!(9, g.get_max_flow(0, 5));
+    g.add_edge(0, 1, &10).unwrap();
+    g.add_edge(0, 2, &3).unwrap();
+    g.add_edge(1, 2, &2).unwrap();
+    g.add_edge(1, 3, &5).unwrap();
+    g.add_edge(2, 4, &7).unwrap();
+    g.add_edge(3, 4, &1).unwrap();
+    g.add_edge(3, 5, &3).unwrap();
+    g.add_edge(4, 5, &9).unwrap();
+    assert_eq!(9, g.get_max_flow(0, 5).unwrap());
 }
\ No newline at end of file